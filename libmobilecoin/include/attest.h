@@ -26,6 +26,13 @@ typedef struct _McMrSignerVerifier McMrSignerVerifier;
 /// criteria specified.
 typedef struct _McVerifier McVerifier;
 
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  /// Only production enclaves are accepted.
+  McDebugEnclavePolicyProductionOnly = 0,
+  /// Debug enclaves are accepted as well, e.g. for test networks.
+  McDebugEnclavePolicyAllowDebug = 1,
+} McDebugEnclavePolicy;
+
 typedef struct _McAttestAke McAttestAke;
 
 /* ==== McMrEnclaveVerifier ==== */
@@ -126,6 +133,27 @@ MC_ATTRIBUTE_NONNULL(1, 2);
 /// settings.
 McVerifier* MC_NULLABLE mc_verifier_create();
 
+/// Construct a new builder using the baked-in IAS root certificates, accepting
+/// debug enclaves only if `debug_enclave_policy` allows it, regardless of the
+/// build settings.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `debug_enclave_policy` is not a known policy.
+McVerifier* MC_NULLABLE mc_verifier_create_with_debug_enclave_policy(
+  McDebugEnclavePolicy debug_enclave_policy,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+);
+
+/// Returns the debug enclave policy the verifier was constructed with.
+///
+/// A panic is reported as 0, i.e. `McDebugEnclavePolicyProductionOnly`,
+/// the same value as a verifier that rejects debug enclaves.
+McDebugEnclavePolicy mc_verifier_get_debug_enclave_policy(
+  const McVerifier* MC_NONNULL verifier
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 void mc_verifier_free(
   McVerifier* MC_NULLABLE verifier
 );
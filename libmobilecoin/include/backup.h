@@ -0,0 +1,84 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+#ifndef BACKUP_H_
+#define BACKUP_H_
+
+#include "common.h"
+#include "keys.h"
+
+/* ==================== Account Backup ==================== */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ==== Types ==== */
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  /// 32 bytes of `RootIdentity` entropy.
+  McAccountBackupEntropyTypeRootEntropy = 0,
+  /// BIP39 mnemonic entropy, from which the account key is derived via SLIP-0010.
+  McAccountBackupEntropyTypeBip39Entropy = 1,
+} McAccountBackupEntropyType;
+
+typedef struct {
+  McAccountBackupEntropyType entropy_type;
+  const McSecret* MC_NONNULL entropy;
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info;
+} McAccountBackup;
+
+/* ==== McAccountBackup ==== */
+
+/// Encrypts `backup` under `passphrase`. The result is safe to store with an
+/// untrusted party, to the extent that the passphrase is strong.
+///
+/// # Preconditions
+///
+/// * `backup.entropy_type` - must be a valid `McAccountBackupEntropyType`.
+/// * `backup.entropy` - must hold 32 bytes of root entropy, or 16, 20, 24, 28,
+///   or 32 bytes of BIP39 entropy, matching `backup.entropy_type`.
+/// * `passphrase` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+McData* MC_NULLABLE mc_account_backup_create(
+  const McAccountBackup* MC_NONNULL backup,
+  const char* MC_NONNULL passphrase,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Decrypts a backup created by `mc_account_backup_create`.
+///
+/// `out_fog_report_url`, `out_fog_report_id`, and `out_fog_authority_spki` are
+/// set to null when the backup was created without fog info.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::UnsupportedBackupVersion`
+/// * `LibMcError::Aead` - the passphrase is wrong or the backup was modified.
+bool mc_account_backup_open(
+  const McBuffer* MC_NONNULL backup_bytes,
+  const char* MC_NONNULL passphrase,
+  McAccountBackupEntropyType* MC_NONNULL out_entropy_type,
+  McSecret* MC_NULLABLE * MC_NONNULL out_entropy,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_url,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_id,
+  McData* MC_NULLABLE * MC_NULLABLE out_fog_authority_spki,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 4);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* !BACKUP_H_ */
@@ -9,6 +9,21 @@
 extern "C" {
 #endif
 
+/* ==== Types ==== */
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  McBip39LanguageEnglish = 0,
+  McBip39LanguageChineseSimplified = 1,
+  McBip39LanguageChineseTraditional = 2,
+  McBip39LanguageFrench = 3,
+  McBip39LanguageItalian = 4,
+  McBip39LanguageJapanese = 5,
+  McBip39LanguageKorean = 6,
+  McBip39LanguageSpanish = 7,
+} McBip39Language;
+
+typedef struct _McBip39MnemonicValidation McBip39MnemonicValidation;
+
 /* ==== McBip39 ==== */
 
 /// # Preconditions
@@ -20,6 +35,46 @@ char* MC_NULLABLE mc_bip39_mnemonic_from_entropy(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/// # Preconditions
+///
+/// * `entropy` - length must be a multiple of 4 and between 16 and 32,
+///   inclusive, in bytes.
+/// * `language` - must be a valid `McBip39Language`.
+char* MC_NULLABLE mc_bip39_mnemonic_from_entropy_with_language(
+  const McBuffer* MC_NONNULL entropy,
+  McBip39Language language
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Generates a new mnemonic of `word_count` words from fresh entropy. Uses
+/// `rng_callback` if provided, or else the library's own CSPRNG.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+char* MC_NULLABLE mc_bip39_mnemonic_generate(
+  size_t word_count,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+);
+
+/// Generates a new mnemonic of `word_count` words from fresh entropy. Uses
+/// `rng_callback` if provided, or else the library's own CSPRNG.
+///
+/// # Preconditions
+///
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+char* MC_NULLABLE mc_bip39_mnemonic_generate_with_language(
+  size_t word_count,
+  McBip39Language language,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+);
+
 /// # Preconditions
 ///
 /// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
@@ -35,6 +90,60 @@ ssize_t mc_bip39_entropy_from_mnemonic(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `out_entropy` - must be null or else length must be >= `entropy.len`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+ssize_t mc_bip39_entropy_from_mnemonic_with_language(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  McMutableBuffer* MC_NULLABLE out_entropy,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Like `mc_bip39_entropy_from_mnemonic_with_language`, but the entropy is
+/// returned as a secret rather than copied into foreign memory.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McSecret* MC_NULLABLE mc_bip39_entropy_from_mnemonic_to_secret(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Finds the wordlist in which `mnemonic` is a valid phrase, checksum
+/// included.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AmbiguousInput` - `mnemonic` is valid in more than one
+///   language.
+/// * `LibMcError::InvalidInput`
+bool mc_bip39_mnemonic_detect_language(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language* MC_NONNULL out_language,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
 /// # Preconditions
 ///
 /// * `prefix` - must be a nul-terminated C string containing valid UTF-8.
@@ -43,6 +152,89 @@ char* MC_NULLABLE mc_bip39_words_by_prefix(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/// # Preconditions
+///
+/// * `prefix` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+char* MC_NULLABLE mc_bip39_words_by_prefix_with_language(
+  const char* MC_NONNULL prefix,
+  McBip39Language language
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McBip39MnemonicValidation ==== */
+
+/// Validates `mnemonic` word by word against the wordlist for `language`.
+/// Unlike `mc_bip39_entropy_from_mnemonic_with_language`, this does not stop
+/// at the first problem, so the result can describe every unknown word.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+McBip39MnemonicValidation* MC_NULLABLE mc_bip39_mnemonic_validate(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+void mc_bip39_mnemonic_validation_free(
+  McBip39MnemonicValidation* MC_NULLABLE validation
+);
+
+bool mc_bip39_mnemonic_validation_is_valid(
+  const McBip39MnemonicValidation* MC_NONNULL validation
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+size_t mc_bip39_mnemonic_validation_get_word_count(
+  const McBip39MnemonicValidation* MC_NONNULL validation
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Whether the phrase has 12, 15, 18, 21 or 24 words.
+bool mc_bip39_mnemonic_validation_is_word_count_valid(
+  const McBip39MnemonicValidation* MC_NONNULL validation
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Whether the phrase has a valid word count and only known words, but its
+/// checksum does not match, i.e. words are swapped or misremembered.
+bool mc_bip39_mnemonic_validation_has_checksum_error(
+  const McBip39MnemonicValidation* MC_NONNULL validation
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+size_t mc_bip39_mnemonic_validation_get_unknown_word_count(
+  const McBip39MnemonicValidation* MC_NONNULL validation
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the zero-based position in the phrase of the `index`th unknown
+/// word.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the unknown word count.
+size_t mc_bip39_mnemonic_validation_get_unknown_word_position(
+  const McBip39MnemonicValidation* MC_NONNULL validation,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the closest wordlist entries to the `index`th unknown word as a
+/// comma-separated list, nearest first. The list is empty when no entry is
+/// close enough to suggest.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the unknown word count.
+char* MC_NULLABLE mc_bip39_mnemonic_validation_get_unknown_word_suggestions(
+  const McBip39MnemonicValidation* MC_NONNULL validation,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 
 #ifdef __cplusplus
 }
@@ -46,12 +46,17 @@ typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
 
   McErrorCodeInvalidInput = 100,
   McErrorCodeInvalidOutput = 101,
+  McErrorCodeUnexpectedWrapperType = 102,
+  McErrorCodeInvalidChecksum = 103,
+  McErrorCodeMalformedKey = 104,
+  McErrorCodeAmbiguousInput = 105,
 
   McErrorCodeAttestationVerificationFailed = 200,
 
   McErrorCodeAead = 300,
   McErrorCodeCipher = 301,
   McErrorCodeUnsupportedCryptoBoxVersion = 302,
+  McErrorCodeUnsupportedBackupVersion = 303,
 
   McErrorCodeTransactionCrypto = 400,
 
@@ -98,6 +103,31 @@ ssize_t mc_data_get_bytes(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/* ==== McSecret ==== */
+
+/// Key material held in Rust-owned memory that is zeroized when the secret is
+/// freed. Unlike `McData`, the bytes are only handed back to foreign code by an
+/// explicit call to `mc_secret_export_bytes`.
+typedef struct _McSecret McSecret;
+
+/// Copies `bytes` into a new secret. The caller remains responsible for
+/// clearing its own copy.
+McSecret* MC_NULLABLE mc_secret_create(
+  const McBuffer* MC_NONNULL bytes
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+void mc_secret_free(McSecret* MC_NULLABLE secret);
+
+/// # Preconditions
+///
+/// * `out_bytes` - must be null or else length must be >= `secret.len`.
+ssize_t mc_secret_export_bytes(
+  const McSecret* MC_NONNULL secret,
+  McMutableBuffer* MC_NULLABLE out_bytes
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 /* ==== McRngCallback ==== */
 
 typedef struct {
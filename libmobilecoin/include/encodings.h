@@ -4,6 +4,7 @@
 #define ENCODINGS_H_
 
 #include "common.h"
+#include "keys.h"
 
 /* ==================== Encodings ==================== */
 
@@ -11,6 +12,31 @@
 extern "C" {
 #endif
 
+/* ==== Types ==== */
+
+typedef struct {
+  const McPublicAddress* MC_NONNULL public_address;
+  uint64_t value;
+  uint64_t token_id;
+  const char* MC_NONNULL memo;
+  uint64_t payment_id;
+} McPaymentRequest;
+
+typedef struct {
+  /// Legacy 32-byte `RootIdentity` entropy. Exactly one of `root_entropy`
+  /// and `bip39_entropy` must be set.
+  const McBuffer* MC_NULLABLE root_entropy;
+  /// BIP39 entropy: a multiple of 4 bytes, between 16 and 32 inclusive.
+  const McBuffer* MC_NULLABLE bip39_entropy;
+  /// 32-byte `CompressedRistrettoPublic`
+  const McBuffer* MC_NONNULL tx_out_public_key;
+  const char* MC_NONNULL memo;
+} McTransferPayload;
+
+typedef struct _McDecodedPaymentRequest McDecodedPaymentRequest;
+
+typedef struct _McDecodedTransferPayload McDecodedTransferPayload;
+
 /* ==== PrintableWrapper ==== */
 
 /// # Preconditions
@@ -38,6 +64,189 @@ ssize_t mc_printable_wrapper_b58_decode(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/* ==== PublicAddress ==== */
+
+/// # Preconditions
+///
+/// * `public_address` - must be a valid `PublicAddress`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+char* MC_NULLABLE mc_public_address_to_b58(
+  const McPublicAddress* MC_NONNULL public_address,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Decodes a b58 `PrintableWrapper` holding a public address. The fog out
+/// parameters are set to null if the address has no fog info.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid UTF-8.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+bool mc_public_address_from_b58(
+  const char* MC_NONNULL b58_encoded_string,
+  McMutableBuffer* MC_NONNULL out_view_public_key,
+  McMutableBuffer* MC_NONNULL out_spend_public_key,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_url,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_id,
+  McData* MC_NULLABLE * MC_NULLABLE out_fog_authority_sig,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+/* ==== PaymentRequest ==== */
+
+/// # Preconditions
+///
+/// * `payment_request` - must be a valid `PaymentRequest`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+char* MC_NULLABLE mc_payment_request_to_b58(
+  const McPaymentRequest* MC_NONNULL payment_request,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Decodes a b58 `PrintableWrapper` holding a payment request, the inverse of
+/// `mc_payment_request_to_b58`.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+McDecodedPaymentRequest* MC_NULLABLE mc_payment_request_from_b58(
+  const char* MC_NONNULL b58_encoded_string,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+void mc_decoded_payment_request_free(
+  McDecodedPaymentRequest* MC_NULLABLE payment_request
+);
+
+/// Gets the requested public address. The fog out parameters are set to null
+/// if the address has no fog info.
+///
+/// # Preconditions
+///
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+bool mc_decoded_payment_request_get_public_address(
+  const McDecodedPaymentRequest* MC_NONNULL payment_request,
+  McMutableBuffer* MC_NONNULL out_view_public_key,
+  McMutableBuffer* MC_NONNULL out_spend_public_key,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_url,
+  char* MC_NULLABLE * MC_NULLABLE out_fog_report_id,
+  McData* MC_NULLABLE * MC_NULLABLE out_fog_authority_sig
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+uint64_t mc_decoded_payment_request_get_value(
+  const McDecodedPaymentRequest* MC_NONNULL payment_request
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+uint64_t mc_decoded_payment_request_get_token_id(
+  const McDecodedPaymentRequest* MC_NONNULL payment_request
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+char* MC_NULLABLE mc_decoded_payment_request_get_memo(
+  const McDecodedPaymentRequest* MC_NONNULL payment_request
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+uint64_t mc_decoded_payment_request_get_payment_id(
+  const McDecodedPaymentRequest* MC_NONNULL payment_request
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== TransferPayload ==== */
+
+/// # Preconditions
+///
+/// * `transfer_payload` - must be a valid `TransferPayload`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::MalformedKey`
+char* MC_NULLABLE mc_transfer_payload_to_b58(
+  const McTransferPayload* MC_NONNULL transfer_payload,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Decodes a b58 `PrintableWrapper` holding a transfer payload, the inverse of
+/// `mc_transfer_payload_to_b58`.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+McDecodedTransferPayload* MC_NULLABLE mc_transfer_payload_from_b58(
+  const char* MC_NONNULL b58_encoded_string,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+void mc_decoded_transfer_payload_free(
+  McDecodedTransferPayload* MC_NULLABLE transfer_payload
+);
+
+/// Returns the legacy 32-byte `RootIdentity` entropy, or null if the payload
+/// holds BIP39 entropy instead.
+McData* MC_NULLABLE mc_decoded_transfer_payload_get_root_entropy(
+  const McDecodedTransferPayload* MC_NONNULL transfer_payload
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the BIP39 entropy, or null if the payload holds legacy root
+/// entropy instead.
+McData* MC_NULLABLE mc_decoded_transfer_payload_get_bip39_entropy(
+  const McDecodedTransferPayload* MC_NONNULL transfer_payload
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `out_tx_out_public_key` - length must be >= 32.
+bool mc_decoded_transfer_payload_get_tx_out_public_key(
+  const McDecodedTransferPayload* MC_NONNULL transfer_payload,
+  McMutableBuffer* MC_NONNULL out_tx_out_public_key
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+char* MC_NULLABLE mc_decoded_transfer_payload_get_memo(
+  const McDecodedTransferPayload* MC_NONNULL transfer_payload
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,112 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+#ifndef FAKE_FOG_INGEST_H_
+#define FAKE_FOG_INGEST_H_
+
+#include "common.h"
+#include "fog.h"
+
+/* ==================== Fake Fog Ingest ==================== */
+
+/// A stand-in for fog ingest and the fog report server, for offline tests of
+/// fog-enabled transaction building. These functions are only present in
+/// builds with the `fake-fog-ingest` cargo feature, so this header is not part
+/// of libmobilecoin.h.
+///
+/// Real report responses carry an ingest attestation report, signed by a key
+/// that no test can produce outside of an SGX simulation build. A
+/// `McFogResolver` told to trust a fake ingest with
+/// `mc_fog_resolver_trust_fake_fog_ingest` accepts the responses signed by its
+/// fog authority key in place of attestation.
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ==== Types ==== */
+
+typedef struct _McFakeFogIngest McFakeFogIngest;
+
+/* ==== McFakeFogIngest ==== */
+
+/// Generates a new ingest keypair and fog authority keypair.
+McFakeFogIngest* MC_NULLABLE mc_fake_fog_ingest_create(
+  McRngCallback* MC_NULLABLE rng_callback
+);
+
+void mc_fake_fog_ingest_free(
+  McFakeFogIngest* MC_NULLABLE fake_fog_ingest
+);
+
+/// # Preconditions
+///
+/// * `out_ingest_public_key` - length must be >= 32.
+bool mc_fake_fog_ingest_get_public_key(
+  const McFakeFogIngest* MC_NONNULL fake_fog_ingest,
+  McMutableBuffer* MC_NONNULL out_ingest_public_key
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Returns the DER-encoded subjectPublicKeyInfo of the fog authority key, to
+/// create fog-enabled account keys served by this fake ingest.
+McData* MC_NULLABLE mc_fake_fog_ingest_get_authority_spki(
+  const McFakeFogIngest* MC_NONNULL fake_fog_ingest
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns a binary-serialized `report.ReportResponse` Protobuf advertising
+/// the ingest public key under `report_id`, signed by the fog authority key.
+///
+/// It is accepted only by a `McFogResolver` that trusts this fake ingest.
+///
+/// # Preconditions
+///
+/// * `report_id` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McData* MC_NULLABLE mc_fake_fog_ingest_create_report_response(
+  const McFakeFogIngest* MC_NONNULL fake_fog_ingest,
+  const char* MC_NONNULL report_id,
+  uint64_t pubkey_expiry,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Decrypts the fog hint of a `TxOut` and writes out the view public key of
+/// the subaddress it was sent to.
+///
+/// # Preconditions
+///
+/// * `tx_out_proto_bytes` - must be a valid binary-serialized `external.TxOut` Protobuf.
+/// * `out_view_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the fog hint was not encrypted for this ingest keypair.
+bool mc_fake_fog_ingest_decrypt_fog_hint(
+  const McFakeFogIngest* MC_NONNULL fake_fog_ingest,
+  const McBuffer* MC_NONNULL tx_out_proto_bytes,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McMutableBuffer* MC_NONNULL out_view_public_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 4);
+
+/* ==== Test Fog Resolver Configuration ==== */
+
+/// Makes `fog_resolver` accept the report responses produced by
+/// `fake_fog_ingest`, skipping attestation. Responses from any other fog
+/// authority are then no longer accepted by `fog_resolver`.
+void mc_fog_resolver_trust_fake_fog_ingest(
+  McFogResolver* MC_NONNULL fog_resolver,
+  const McFakeFogIngest* MC_NONNULL fake_fog_ingest
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* !FAKE_FOG_INGEST_H_ */
@@ -16,10 +16,70 @@ extern "C" {
 
 typedef struct _McFogResolver McFogResolver;
 
+typedef struct _McFogReportUrls McFogReportUrls;
+
 typedef struct _McFullyValidatedFogPubkey McFullyValidatedFogPubkey;
 
 typedef struct _McFogRng McFogRng;
 
+typedef struct _McFogViewQuery McFogViewQuery;
+
+typedef struct _McFogViewQueryResult McFogViewQueryResult;
+
+typedef struct _McFogRngSet McFogRngSet;
+
+typedef struct _McFogKeyImageQuery McFogKeyImageQuery;
+
+typedef struct _McFogKeyImageResults McFogKeyImageResults;
+
+typedef struct _McFogMerkleProofQuery McFogMerkleProofQuery;
+
+/// Created and consumed by the functions in transaction.h.
+typedef struct _McTransactionBuilderRing McTransactionBuilderRing;
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  McFogKeyImageStatusNotSpent = 0,
+  McFogKeyImageStatusSpent = 1,
+  /// Fog ledger could not check the key image, e.g. because it was malformed.
+  McFogKeyImageStatusError = 2,
+} McFogKeyImageStatus;
+
+typedef struct {
+  McFogKeyImageStatus status;
+  /// Index of the block in which the key image was spent. Only meaningful
+  /// when `status` is `McFogKeyImageStatusSpent`.
+  uint64_t spent_at;
+  /// Timestamp of the block at `spent_at`, in seconds since the Unix epoch.
+  /// Zero when the status is not spent or fog has no timestamp for it.
+  uint64_t timestamp;
+} McFogKeyImageResult;
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  /// The check depends on an earlier one that failed, e.g. there is no report
+  /// to check the attestation of.
+  McFogReportCheckStatusNotChecked = 0,
+  McFogReportCheckStatusPassed = 1,
+  McFogReportCheckStatusFailed = 2,
+} McFogReportCheckStatus;
+
+typedef struct {
+  /// Whether the response contains a report with the recipient's report id.
+  bool report_id_found;
+  /// The block index at which the report's fog pubkey expires, or 0 if the
+  /// report was not found.
+  uint64_t pubkey_expiry;
+  /// Validity of the response's certificate chain, and verification of its
+  /// root against the recipient's fog authority signature.
+  McFogReportCheckStatus certificate_chain_status;
+  /// Verification of the signature over the reports by the chain's leaf
+  /// certificate.
+  McFogReportCheckStatus report_signature_status;
+  /// Verification of the ingest enclave's attestation report.
+  McFogReportCheckStatus attestation_status;
+  /// Whether the fog pubkey expires after the current block height.
+  McFogReportCheckStatus expiry_status;
+} McFogReportValidationResult;
+
 /* ==== McFogResolver ==== */
 
 McFogResolver* MC_NULLABLE mc_fog_resolver_create(
@@ -60,6 +120,127 @@ bool mc_fog_resolver_add_report_response(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3);
 
+/// Returns the time at which the response for `report_url` was added to the
+/// resolver, in seconds since the Unix epoch, or 0 if there is none.
+///
+/// # Preconditions
+///
+/// * `report_url` - must be a nul-terminated C string containing a valid Fog report uri.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+uint64_t mc_fog_resolver_get_report_fetched_at(
+  const McFogResolver* MC_NONNULL fog_resolver,
+  const char* MC_NONNULL report_url,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Returns the URLs of cached report responses that should be fetched again
+/// before sending at `current_block_height`. A URL is stale if its response
+/// has no reports or if ANY of its reports' fog pubkeys has expired, even when
+/// the report a particular recipient uses is still valid.
+McFogReportUrls* MC_NULLABLE mc_fog_resolver_get_stale_report_urls(
+  const McFogResolver* MC_NONNULL fog_resolver,
+  uint64_t current_block_height
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Serializes the cached report responses, with the time each was fetched.
+///
+/// # Preconditions
+///
+/// * `out_report_responses_proto_bytes` - must be null or else length must be >= `encoded.len`.
+ssize_t mc_fog_resolver_serialize_report_responses(
+  const McFogResolver* MC_NONNULL fog_resolver,
+  McMutableBuffer* MC_NULLABLE out_report_responses_proto_bytes
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Adds report responses serialized with
+/// `mc_fog_resolver_serialize_report_responses` to the resolver, keeping their
+/// original fetch times. A cached response for the same URL is replaced.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_fog_resolver_deserialize_report_responses(
+  McFogResolver* MC_NONNULL fog_resolver,
+  const McBuffer* MC_NONNULL report_responses_proto_bytes,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Returns the URLs in `report_urls` that have no cached response.
+McFogReportUrls* MC_NULLABLE mc_fog_resolver_get_missing_report_urls(
+  const McFogResolver* MC_NONNULL fog_resolver,
+  const McFogReportUrls* MC_NONNULL report_urls
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/* ==== McFogReportUrls ==== */
+
+/// Creates an empty list, to be filled with the report URLs that must be
+/// fetched before building a transaction.
+McFogReportUrls* MC_NULLABLE mc_fog_report_urls_create();
+
+void mc_fog_report_urls_free(
+  McFogReportUrls* MC_NULLABLE fog_report_urls
+);
+
+/// Adds the fog report URL of `recipient`, if it has fog.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report uri.
+bool mc_fog_report_urls_add_public_address(
+  McFogReportUrls* MC_NONNULL fog_report_urls,
+  const McPublicAddress* MC_NONNULL recipient,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Adds the fog report URL of `account_key`, if it has fog. This covers the
+/// account's change subaddress and gift code subaddresses, which all share the
+/// account's fog info.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report uri.
+bool mc_fog_report_urls_add_account_key(
+  McFogReportUrls* MC_NONNULL fog_report_urls,
+  const McAccountKey* MC_NONNULL account_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Like `mc_fog_report_urls_add_account_key`, but takes an account key handle.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report uri.
+bool mc_fog_report_urls_add_account_key_with_handle(
+  McFogReportUrls* MC_NONNULL fog_report_urls,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+size_t mc_fog_report_urls_get_count(
+  const McFogReportUrls* MC_NONNULL fog_report_urls
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the URL count.
+char* MC_NULLABLE mc_fog_report_urls_get_url(
+  const McFogReportUrls* MC_NONNULL fog_report_urls,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 /* ==== McFullyValidatedFogPubkey ==== */
 
 void mc_fully_validated_fog_pubkey_free(
@@ -151,6 +332,426 @@ bool mc_fog_rng_advance(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
+/// Writes the next `count` outputs of `fog_rng` back to back into
+/// `out_outputs` without advancing it, and returns their total length, i.e.
+/// `count * output.len`.
+///
+/// # Preconditions
+///
+/// * `out_outputs` - must be null or else length must be >= `count * output.len`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `count * output.len` overflows.
+ssize_t mc_fog_rng_peek_many(
+  const McFogRng* MC_NONNULL fog_rng,
+  size_t count,
+  McMutableBuffer* MC_NULLABLE out_outputs,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Advances `fog_rng` by `count` outputs.
+bool mc_fog_rng_advance_by(
+  McFogRng* MC_NONNULL fog_rng,
+  size_t count
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McFogViewQuery ==== */
+
+McFogViewQuery* MC_NULLABLE mc_fog_view_query_create(
+  int64_t start_from_user_event_id,
+  uint64_t start_from_block_index
+);
+
+void mc_fog_view_query_free(
+  McFogViewQuery* MC_NULLABLE fog_view_query
+);
+
+/// Adds a copy of `fog_rng` to the query, which will ask for its next
+/// `search_key_count` outputs. Returns the index used to retrieve the advanced
+/// RNG with `mc_fog_view_query_get_fog_rng`.
+size_t mc_fog_view_query_add_fog_rng(
+  McFogViewQuery* MC_NONNULL fog_view_query,
+  const McFogRng* MC_NONNULL fog_rng,
+  size_t search_key_count
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// # Preconditions
+///
+/// * `index` - must be an index returned by `mc_fog_view_query_add_fog_rng`.
+McFogRng* MC_NULLABLE mc_fog_view_query_get_fog_rng(
+  const McFogViewQuery* MC_NONNULL fog_view_query,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the serialized `fog_view.QueryRequest` Protobuf, to be encrypted
+/// for the fog view enclave with `mc_attest_ake_encrypt`.
+McData* MC_NULLABLE mc_fog_view_query_create_request(
+  const McFogViewQuery* MC_NONNULL fog_view_query
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the serialized `fog_view.QueryRequestAAD` Protobuf, to be passed as
+/// the associated data when encrypting the request.
+McData* MC_NULLABLE mc_fog_view_query_create_request_aad(
+  const McFogViewQuery* MC_NONNULL fog_view_query
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Decrypts the TxOuts found for the query's search keys and advances each of
+/// the query's RNGs past its found outputs. Search results other than `Found`
+/// are ignored, so the corresponding RNGs stay in place and the keys are asked
+/// for again by the next query.
+///
+/// # Preconditions
+///
+/// * `query_response_proto_bytes` - must be the decrypted, binary-serialized
+///   `fog_view.QueryResponse` Protobuf answering this query's request.
+/// * `subaddress_view_private_key` - must be a valid 32-byte Ristretto-format
+///   scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+/// * `LibMcError::TransactionCrypto`
+McFogViewQueryResult* MC_NULLABLE mc_fog_view_query_process_response(
+  McFogViewQuery* MC_NONNULL fog_view_query,
+  const McBuffer* MC_NONNULL query_response_proto_bytes,
+  const McBuffer* MC_NONNULL subaddress_view_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+/* ==== McFogViewQueryResult ==== */
+
+void mc_fog_view_query_result_free(
+  McFogViewQueryResult* MC_NULLABLE fog_view_query_result
+);
+
+uint64_t mc_fog_view_query_result_get_highest_processed_block_count(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+int64_t mc_fog_view_query_result_get_next_start_from_user_event_id(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+size_t mc_fog_view_query_result_get_tx_out_count(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the serialized `external.TxOut` Protobuf at `index`.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+McData* MC_NULLABLE mc_fog_view_query_result_get_tx_out(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+uint64_t mc_fog_view_query_result_get_tx_out_block_index(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+uint64_t mc_fog_view_query_result_get_tx_out_global_index(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+uint64_t mc_fog_view_query_result_get_tx_out_timestamp(
+  const McFogViewQueryResult* MC_NONNULL fog_view_query_result,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McFogRngSet ==== */
+
+McFogRngSet* MC_NULLABLE mc_fog_rng_set_create();
+
+void mc_fog_rng_set_free(
+  McFogRngSet* MC_NULLABLE fog_rng_set
+);
+
+/// # Preconditions
+///
+/// * `out_fog_rng_set_proto_bytes` - must be null or else length must be >= `encoded.len`.
+ssize_t mc_fog_rng_set_serialize_proto(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  McMutableBuffer* MC_NULLABLE out_fog_rng_set_proto_bytes
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+McFogRngSet* MC_NULLABLE mc_fog_rng_set_deserialize_proto(
+  const McBuffer* MC_NONNULL fog_rng_set_proto_bytes,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Creates a query asking for the next `search_key_count` outputs of every RNG
+/// in the set that is not exhausted.
+McFogViewQuery* MC_NULLABLE mc_fog_rng_set_create_query(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t search_key_count,
+  int64_t start_from_user_event_id,
+  uint64_t start_from_block_index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Processes the response to a query created with
+/// `mc_fog_rng_set_create_query`: adds an RNG for each new `RngRecord`, marks
+/// RNGs of decommissioned ingest invocations, decrypts the TxOuts found, and
+/// advances the set's RNGs past them.
+///
+/// # Preconditions
+///
+/// * `query_response_proto_bytes` - must be the decrypted, binary-serialized
+///   `fog_view.QueryResponse` Protobuf.
+/// * `subaddress_view_private_key` - must be a valid 32-byte Ristretto-format
+///   scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+/// * `LibMcError::TransactionCrypto`
+McFogViewQueryResult* MC_NULLABLE mc_fog_rng_set_process_query_response(
+  McFogRngSet* MC_NONNULL fog_rng_set,
+  const McBuffer* MC_NONNULL query_response_proto_bytes,
+  const McBuffer* MC_NONNULL subaddress_view_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+size_t mc_fog_rng_set_get_count(
+  const McFogRngSet* MC_NONNULL fog_rng_set
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+McFogRng* MC_NULLABLE mc_fog_rng_set_get_fog_rng(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+int64_t mc_fog_rng_set_get_ingest_invocation_id(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+uint64_t mc_fog_rng_set_get_start_block(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+bool mc_fog_rng_set_is_decommissioned(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+bool mc_fog_rng_set_is_exhausted(
+  const McFogRngSet* MC_NONNULL fog_rng_set,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McFogKeyImageQuery ==== */
+
+McFogKeyImageQuery* MC_NULLABLE mc_fog_key_image_query_create();
+
+void mc_fog_key_image_query_free(
+  McFogKeyImageQuery* MC_NULLABLE fog_key_image_query
+);
+
+/// Adds `key_image` to the query. Fog ledger only reports spends at or after
+/// `start_block`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_fog_key_image_query_add_key_image(
+  McFogKeyImageQuery* MC_NONNULL fog_key_image_query,
+  const McBuffer* MC_NONNULL key_image,
+  uint64_t start_block,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// Returns the serialized `fog_ledger.CheckKeyImagesRequest` Protobuf, to be
+/// encrypted for the fog ledger enclave with `mc_attest_ake_encrypt`.
+McData* MC_NULLABLE mc_fog_key_image_query_create_request(
+  const McFogKeyImageQuery* MC_NONNULL fog_key_image_query
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McFogKeyImageResults ==== */
+
+/// # Preconditions
+///
+/// * `check_key_images_response_proto_bytes` - must be the decrypted,
+///   binary-serialized `fog_ledger.CheckKeyImagesResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McFogKeyImageResults* MC_NULLABLE mc_fog_key_image_results_create(
+  const McBuffer* MC_NONNULL check_key_images_response_proto_bytes,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+void mc_fog_key_image_results_free(
+  McFogKeyImageResults* MC_NULLABLE fog_key_image_results
+);
+
+/// The number of blocks fog ledger had processed when it answered.
+uint64_t mc_fog_key_image_results_get_num_blocks(
+  const McFogKeyImageResults* MC_NONNULL fog_key_image_results
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+size_t mc_fog_key_image_results_get_count(
+  const McFogKeyImageResults* MC_NONNULL fog_key_image_results
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the result count.
+/// * `out_key_image` - length must be >= 32.
+bool mc_fog_key_image_results_get_result(
+  const McFogKeyImageResults* MC_NONNULL fog_key_image_results,
+  size_t index,
+  McMutableBuffer* MC_NONNULL out_key_image,
+  McFogKeyImageResult* MC_NONNULL out_result
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4);
+
+/* ==== McFogMerkleProofQuery ==== */
+
+/// Creates a query for the membership proofs of a set of outputs. All proofs
+/// are taken as of `merkle_root_block`, so they share one merkle root.
+McFogMerkleProofQuery* MC_NULLABLE mc_fog_merkle_proof_query_create(
+  uint64_t merkle_root_block
+);
+
+void mc_fog_merkle_proof_query_free(
+  McFogMerkleProofQuery* MC_NULLABLE fog_merkle_proof_query
+);
+
+/// Adds the output with global index `tx_out_global_index` to the query.
+bool mc_fog_merkle_proof_query_add_index(
+  McFogMerkleProofQuery* MC_NONNULL fog_merkle_proof_query,
+  uint64_t tx_out_global_index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Returns the serialized `fog_ledger.GetOutputsRequest` Protobuf, to be
+/// encrypted for the fog ledger enclave with `mc_attest_ake_encrypt`.
+McData* MC_NULLABLE mc_fog_merkle_proof_query_create_request(
+  const McFogMerkleProofQuery* MC_NONNULL fog_merkle_proof_query
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Fills `out_ring` with the outputs and membership proofs in the response,
+/// in the order their indices were added to the query. `out_ring` is left
+/// empty if the response fails validation.
+///
+/// # Preconditions
+///
+/// * `get_outputs_response_proto_bytes` - must be the decrypted,
+///   binary-serialized `fog_ledger.GetOutputsResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `out_ring` is not empty, or the response is
+///   malformed, does not answer the query, reports a missing output, or
+///   contains a proof that is invalid or implies a different merkle root than
+///   the others.
+bool mc_fog_merkle_proof_query_process_response(
+  const McFogMerkleProofQuery* MC_NONNULL fog_merkle_proof_query,
+  const McBuffer* MC_NONNULL get_outputs_response_proto_bytes,
+  McTransactionBuilderRing* MC_NONNULL out_ring,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+/* ==== McFogReportValidation ==== */
+
+/// Validates one fog report response for `recipient` with the checks that
+/// `mc_fog_resolver_get_fog_pubkey` runs, and reports the outcome of each
+/// check instead of stopping at the first failure. This is meant for
+/// diagnosing recipients that cannot be sent to.
+///
+/// The certificate chain is checked first; the signature over the reports is
+/// only checked once the chain has passed.
+///
+/// `out_details` is set to a description of each failed check, or to null if
+/// every check passed.
+///
+/// # Preconditions
+///
+/// * `report_response` - must be a valid binary-serialized `report.ReportResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `recipient` has no fog info.
+bool mc_fog_report_response_validate(
+  const McBuffer* MC_NONNULL report_response,
+  const McVerifier* MC_NONNULL fog_report_verifier,
+  const McPublicAddress* MC_NONNULL recipient,
+  uint64_t current_block_height,
+  McFogReportValidationResult* MC_NONNULL out_result,
+  char* MC_NULLABLE * MC_NULLABLE out_details,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
+
 #ifdef __cplusplus
 }
 #endif
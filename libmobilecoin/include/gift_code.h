@@ -0,0 +1,276 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+#ifndef GIFT_CODE_H_
+#define GIFT_CODE_H_
+
+#include "common.h"
+#include "fog.h"
+#include "keys.h"
+#include "transaction.h"
+
+/* ==================== Gift Code ==================== */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ==== Types ==== */
+
+typedef struct _McGiftCode McGiftCode;
+
+/* ==== McGiftCode ==== */
+
+void mc_gift_code_free(
+  McGiftCode* MC_NULLABLE gift_code
+);
+
+/// Creates a transaction builder whose change output will carry a gift code
+/// funding memo. Inputs are then added with `mc_transaction_builder_add_input`
+/// and the outputs with `mc_transaction_builder_fund_gift_code`.
+///
+/// `current_block_height` is used as by `mc_transaction_builder_create`.
+///
+/// # Preconditions
+///
+/// * `funding_note` - must be a nul-terminated C string containing at most 53
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McTransactionBuilder* MC_NULLABLE mc_gift_code_funding_transaction_builder_create(
+  uint64_t fee,
+  uint64_t token_id,
+  uint64_t tombstone_block,
+  uint64_t current_block_height,
+  const McFogResolver* MC_NULLABLE fog_resolver,
+  const char* MC_NONNULL funding_note,
+  uint32_t block_version,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(6);
+
+/// Generates fresh entropy for a new gift code, sends `amount` to the gift
+/// code subaddress of the new gift code account, and sends the change back to
+/// the funder's change subaddress.
+///
+/// The change is the value of the inputs added to the builder, less `amount`
+/// and the fee, so every input must be added before calling this. The funding
+/// transaction is then produced by `mc_transaction_builder_build`.
+///
+/// # Preconditions
+///
+/// * `transaction_builder` - must have been created by
+///   `mc_gift_code_funding_transaction_builder_create` and not previously
+///   consumed by a call to `build`.
+/// * `account_key` - must be a valid account key.
+/// * `memo` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::FogPubkey` - the change subaddress's fog pubkey expires at or
+///   before the `current_block_height` the builder was created with.
+/// * `LibMcError::InvalidInput` - the inputs do not cover `amount` and the fee.
+McGiftCode* MC_NULLABLE mc_transaction_builder_fund_gift_code(
+  McTransactionBuilder* MC_NONNULL transaction_builder,
+  const McAccountKey* MC_NONNULL account_key,
+  uint64_t amount,
+  const char* MC_NONNULL memo,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 4);
+
+/// # Preconditions
+///
+/// * `transaction_builder` - must have been created by
+///   `mc_gift_code_funding_transaction_builder_create` and not previously
+///   consumed by a call to `build`.
+/// * `memo` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::FogPubkey` - the change subaddress's fog pubkey expires at or
+///   before the `current_block_height` the builder was created with.
+/// * `LibMcError::InvalidInput` - the inputs do not cover `amount` and the fee.
+McGiftCode* MC_NULLABLE mc_transaction_builder_fund_gift_code_with_handle(
+  McTransactionBuilder* MC_NONNULL transaction_builder,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  uint64_t amount,
+  const char* MC_NONNULL memo,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 4);
+
+char* MC_NULLABLE mc_gift_code_to_b58(
+  const McGiftCode* MC_NONNULL gift_code
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::MalformedKey`
+McGiftCode* MC_NULLABLE mc_gift_code_from_b58(
+  const char* MC_NONNULL b58_encoded_string,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `out_tx_out_public_key` - length must be >= 32.
+bool mc_gift_code_get_tx_out_public_key(
+  const McGiftCode* MC_NONNULL gift_code,
+  McMutableBuffer* MC_NONNULL out_tx_out_public_key
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+char* MC_NULLABLE mc_gift_code_get_memo(
+  const McGiftCode* MC_NONNULL gift_code
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Checks that `tx_out_proto_bytes` is the gift code `TxOut` and is owned by
+/// the gift code account, then decrypts its amount.
+///
+/// # Preconditions
+///
+/// * `tx_out_proto_bytes` - must be a valid binary-serialized `external.TxOut` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+bool mc_gift_code_get_tx_out_amount(
+  const McGiftCode* MC_NONNULL gift_code,
+  const McBuffer* MC_NONNULL tx_out_proto_bytes,
+  McTxOutAmount* MC_NONNULL out_amount,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+/// # Preconditions
+///
+/// * `account_key_handle` - must be the account that funded the gift code.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `global_index` - must be the global TxOut index of the gift code `TxOut`.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+McData* MC_NULLABLE mc_gift_code_build_cancel_transaction_with_handle(
+  const McGiftCode* MC_NONNULL gift_code,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  const McTransactionBuilderRing* MC_NONNULL ring,
+  size_t real_index,
+  uint64_t global_index,
+  uint64_t fee,
+  uint64_t tombstone_block,
+  const McFogResolver* MC_NULLABLE fog_resolver,
+  uint32_t block_version,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+/// Builds a transaction moving the gift code value, less `fee`, to the default
+/// subaddress of `account_key`, with a gift code sender memo.
+///
+/// # Preconditions
+///
+/// * `account_key` - must be a valid account key.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `sender_note` - must be a nul-terminated C string containing at most 57
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+McData* MC_NULLABLE mc_gift_code_build_claim_transaction(
+  const McGiftCode* MC_NONNULL gift_code,
+  const McAccountKey* MC_NONNULL account_key,
+  const McTransactionBuilderRing* MC_NONNULL ring,
+  size_t real_index,
+  uint64_t fee,
+  uint64_t tombstone_block,
+  const McFogResolver* MC_NULLABLE fog_resolver,
+  const char* MC_NONNULL sender_note,
+  uint32_t block_version,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 8);
+
+/// # Preconditions
+///
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `sender_note` - must be a nul-terminated C string containing at most 57
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Poison`
+/// * `LibMcError::TransactionCrypto`
+McData* MC_NULLABLE mc_gift_code_build_claim_transaction_with_handle(
+  const McGiftCode* MC_NONNULL gift_code,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  const McTransactionBuilderRing* MC_NONNULL ring,
+  size_t real_index,
+  uint64_t fee,
+  uint64_t tombstone_block,
+  const McFogResolver* MC_NULLABLE fog_resolver,
+  const char* MC_NONNULL sender_note,
+  uint32_t block_version,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 8);
+
+/// Builds a transaction returning an unclaimed gift code, less `fee`, to the
+/// change subaddress of `account_key`, with a gift code cancellation memo.
+///
+/// # Preconditions
+///
+/// * `account_key` - must be the account key that funded the gift code.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `global_index` - must be the global TxOut index of the gift code `TxOut`.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+McData* MC_NULLABLE mc_gift_code_build_cancel_transaction(
+  const McGiftCode* MC_NONNULL gift_code,
+  const McAccountKey* MC_NONNULL account_key,
+  const McTransactionBuilderRing* MC_NONNULL ring,
+  size_t real_index,
+  uint64_t global_index,
+  uint64_t fee,
+  uint64_t tombstone_block,
+  const McFogResolver* MC_NULLABLE fog_resolver,
+  uint32_t block_version,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* !GIFT_CODE_H_ */
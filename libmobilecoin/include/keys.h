@@ -3,6 +3,7 @@
 #ifndef KEYS_H_
 #define KEYS_H_
 
+#include "bip39.h"
 #include "common.h"
 
 /* ==================== Account Keys ==================== */
@@ -37,6 +38,211 @@ typedef struct {
   const McPublicAddressFogInfo* MC_NULLABLE fog_info;
 } McPublicAddress;
 
+typedef struct _McAccountKeyHandle McAccountKeyHandle;
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  McFogAuthoritySigStatusValid = 0,
+  McFogAuthoritySigStatusNoFogInfo = 1,
+  McFogAuthoritySigStatusReportUrlMismatch = 2,
+  McFogAuthoritySigStatusReportIdMismatch = 3,
+  McFogAuthoritySigStatusMalformedSignature = 4,
+  McFogAuthoritySigStatusSignatureMismatch = 5,
+} McFogAuthoritySigStatus;
+
+typedef struct {
+  uint64_t default_subaddress_index;
+  uint64_t change_subaddress_index;
+  uint64_t gift_code_subaddress_index;
+} McReservedSubaddressIndices;
+
+typedef enum MC_ATTRIBUTE_ENUM_CLOSED {
+  McReservedSubaddressDefault = 0,
+  McReservedSubaddressChange = 1,
+  McReservedSubaddressGiftCode = 2,
+} McReservedSubaddress;
+
+/* ==== McAccountKeyHandle ==== */
+
+/// # Preconditions
+///
+/// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `spend_private_key` - must be a valid 32-byte Ristretto-format scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McAccountKeyHandle* MC_NULLABLE mc_account_key_handle_create(
+  const McBuffer* MC_NONNULL view_private_key,
+  const McBuffer* MC_NONNULL spend_private_key,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+/// # Preconditions
+///
+/// * `root_entropy` - must be 32 bytes in length.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McAccountKeyHandle* MC_NULLABLE mc_account_key_handle_create_from_root_entropy(
+  const McBuffer* MC_NONNULL root_entropy,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McAccountKeyHandle* MC_NULLABLE mc_account_key_handle_create_from_mnemonic(
+  const char* MC_NONNULL mnemonic,
+  uint32_t account_index,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McAccountKeyHandle* MC_NULLABLE mc_account_key_handle_create_from_mnemonic_with_language(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  uint32_t account_index,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `spend_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McAccountKeyHandle* MC_NULLABLE mc_account_key_handle_create_from_secrets(
+  const McSecret* MC_NONNULL view_private_key,
+  const McSecret* MC_NONNULL spend_private_key,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+void mc_account_key_handle_free(
+  McAccountKeyHandle* MC_NULLABLE account_key_handle
+);
+
+/// # Preconditions
+///
+/// * `out_subaddress_view_private_key` - length must be >= 32.
+/// * `out_subaddress_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+bool mc_account_key_handle_get_subaddress_private_keys(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  uint64_t subaddress_index,
+  McMutableBuffer* MC_NONNULL out_subaddress_view_private_key,
+  McMutableBuffer* MC_NONNULL out_subaddress_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4);
+
+/// Like `mc_account_key_handle_get_subaddress_private_keys`, but the keys
+/// are returned as secrets rather than copied into foreign memory.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+bool mc_account_key_handle_get_subaddress_private_keys_to_secrets(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  uint64_t subaddress_index,
+  McSecret* MC_NULLABLE * MC_NONNULL out_subaddress_view_private_key,
+  McSecret* MC_NULLABLE * MC_NONNULL out_subaddress_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4);
+
+/// # Preconditions
+///
+/// * `out_subaddress_view_public_key` - length must be >= 32.
+/// * `out_subaddress_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+bool mc_account_key_handle_get_public_address_public_keys(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  uint64_t subaddress_index,
+  McMutableBuffer* MC_NONNULL out_subaddress_view_public_key,
+  McMutableBuffer* MC_NONNULL out_subaddress_spend_public_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4);
+
+/* ==== Reserved Subaddresses ==== */
+
+/// Returns the subaddress indices that every account reserves for incoming
+/// payments, change and gift codes.
+bool mc_account_key_get_reserved_subaddress_indices(
+  McReservedSubaddressIndices* MC_NONNULL out_indices
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `account_key` - must be a valid account key.
+/// * `reserved_subaddress` - must be a valid `McReservedSubaddress`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+bool mc_account_key_get_reserved_subaddress_keys(
+  const McAccountKey* MC_NONNULL account_key,
+  McReservedSubaddress reserved_subaddress,
+  McMutableBuffer* MC_NONNULL out_view_private_key,
+  McMutableBuffer* MC_NONNULL out_spend_private_key,
+  McMutableBuffer* MC_NONNULL out_view_public_key,
+  McMutableBuffer* MC_NONNULL out_spend_public_key
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4, 5, 6);
+
+/// # Preconditions
+///
+/// * `reserved_subaddress` - must be a valid `McReservedSubaddress`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+bool mc_account_key_handle_get_reserved_subaddress_keys(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  McReservedSubaddress reserved_subaddress,
+  McMutableBuffer* MC_NONNULL out_view_private_key,
+  McMutableBuffer* MC_NONNULL out_spend_private_key,
+  McMutableBuffer* MC_NONNULL out_view_public_key,
+  McMutableBuffer* MC_NONNULL out_spend_public_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4, 5, 6);
+
 /* ==== AccountKey ==== */
 
 /// # Preconditions
@@ -66,6 +272,19 @@ bool mc_account_private_keys_from_root_entropy(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3);
 
+/// Like `mc_account_private_keys_from_root_entropy`, but both the entropy and
+/// the derived keys stay in Rust-owned memory.
+///
+/// # Preconditions
+///
+/// * `root_entropy` - must hold 32 bytes.
+bool mc_account_private_keys_from_root_entropy_to_secrets(
+  const McSecret* MC_NONNULL root_entropy,
+  McSecret* MC_NULLABLE * MC_NONNULL out_view_private_key,
+  McSecret* MC_NULLABLE * MC_NONNULL out_spend_private_key
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3);
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -92,6 +311,22 @@ bool mc_account_key_get_public_address_fog_authority_sig(
 )
 MC_ATTRIBUTE_NONNULL(1, 3);
 
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+/// * `out_fog_authority_sig` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+bool mc_account_key_get_public_address_fog_authority_sig_with_handle(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  uint64_t subaddress_index,
+  McMutableBuffer* MC_NONNULL out_fog_authority_sig,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3);
+
 /// # Preconditions
 ///
 /// * `public_address` - must be a valid `PublicAddress`.
@@ -107,6 +342,35 @@ bool mc_account_key_get_short_address_hash(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3);
 
+/* ==== Fog Authority Signature Verification ==== */
+
+/// Checks that `public_address` was issued by the fog operator with
+/// `fog_authority_spki`, and optionally that it uses the expected report
+/// server. Report URLs are compared in their normalized `FogUri` form. A
+/// mismatch is not an error: the reason is reported through `out_status`.
+///
+/// # Preconditions
+///
+/// * `public_address` - must be a valid `PublicAddress`.
+/// * `expected_report_url` - must be null or else a nul-terminated C string
+///   containing valid UTF-8.
+/// * `expected_report_id` - must be null or else a nul-terminated C string
+///   containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `expected_report_url` is not a valid Fog
+///   report uri.
+bool mc_public_address_verify_fog_authority_sig(
+  const McPublicAddress* MC_NONNULL public_address,
+  const McBuffer* MC_NONNULL fog_authority_spki,
+  const char* MC_NULLABLE expected_report_url,
+  const char* MC_NULLABLE expected_report_id,
+  McFogAuthoritySigStatus* MC_NONNULL out_status,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 5);
+
 #ifdef __cplusplus
 }
 #endif
@@ -7,11 +7,14 @@
 #include "crypto.h"
 #include "keys.h"
 #include "attest.h"
+#include "backup.h"
 #include "encodings.h"
 #include "fog.h"
 #include "transaction.h"
+#include "gift_code.h"
 #include "bip39.h"
 #include "slip10.h"
+#include "slip39.h"
 #include "chacha20_rng.h"
 
 #endif /* !LIBMOBILECOIN_H_ */
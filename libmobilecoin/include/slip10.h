@@ -1,7 +1,9 @@
 #ifndef SLIP10_H_
 #define SLIP10_H_
 
+#include "bip39.h"
 #include "common.h"
+#include "keys.h"
 
 /* ==================== SLIP10 ==================== */
 
@@ -9,6 +11,21 @@
 extern "C" {
 #endif
 
+/* ==== Types ==== */
+
+/// Callback used by `mc_slip10_discover_accounts` to ask foreign code whether
+/// the account at `account_index` has ever been used.
+typedef struct {
+  bool (* MC_NONNULL has_activity)(
+    void* MC_NULLABLE context,
+    uint32_t account_index,
+    const McAccountKeyHandle* MC_NONNULL account_key
+  );
+  void* MC_NULLABLE context;
+} McSlip10AccountActivityCallback;
+
+typedef struct _McSlip10DiscoveredAccounts McSlip10DiscoveredAccounts;
+
 /* ==== McSlip10 ==== */
 
 /// # Preconditions
@@ -29,6 +46,151 @@ bool mc_slip10_account_private_keys_from_mnemonic(
 )
 MC_ATTRIBUTE_NONNULL(1, 3, 4);
 
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_slip10_account_private_keys_from_mnemonic_with_language(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  uint32_t account_index,
+  McMutableBuffer* MC_NONNULL out_view_private_key,
+  McMutableBuffer* MC_NONNULL out_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 4, 5);
+
+/// Derives account keys from a mnemonic and a BIP39 passphrase (the "25th
+/// word"). A null or empty `passphrase` derives the same keys as
+/// `mc_slip10_account_private_keys_from_mnemonic_with_language`.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_slip10_account_private_keys_from_mnemonic_with_passphrase(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  const char* MC_NULLABLE passphrase,
+  uint32_t account_index,
+  McMutableBuffer* MC_NONNULL out_view_private_key,
+  McMutableBuffer* MC_NONNULL out_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 5, 6);
+
+/// Like `mc_slip10_account_private_keys_from_mnemonic_with_passphrase`, but
+/// the keys are returned as secrets rather than copied into foreign memory.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_slip10_account_private_keys_from_mnemonic_to_secrets(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  const char* MC_NULLABLE passphrase,
+  uint32_t account_index,
+  McSecret* MC_NULLABLE * MC_NONNULL out_view_private_key,
+  McSecret* MC_NULLABLE * MC_NONNULL out_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 5, 6);
+
+/// Derives account keys using `master_secret` directly as the SLIP-0010 seed,
+/// as SLIP-0039 prescribes for a master secret recovered with
+/// `mc_slip39_combine_shares`.
+///
+/// Note that this is not the derivation used for a mnemonic: if the shares
+/// hold BIP39 entropy, convert the recovered secret back into a mnemonic
+/// instead.
+///
+/// # Preconditions
+///
+/// * `master_secret` - must hold between 16 and 64 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_slip10_account_private_keys_from_master_secret(
+  const McSecret* MC_NONNULL master_secret,
+  uint32_t account_index,
+  McSecret* MC_NULLABLE * MC_NONNULL out_view_private_key,
+  McSecret* MC_NULLABLE * MC_NONNULL out_spend_private_key,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 3, 4);
+
+/* ==== McSlip10AccountDiscovery ==== */
+
+/// Derives accounts `0..max_account_count` from `mnemonic` in order, calling
+/// `callback` for each, and stops once `gap_limit` consecutive accounts have
+/// reported no activity.
+///
+/// The `McAccountKeyHandle` passed to `callback` is only valid for the
+/// duration of the call.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+/// * `gap_limit` - must be greater than 0.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+McSlip10DiscoveredAccounts* MC_NULLABLE mc_slip10_discover_accounts(
+  const char* MC_NONNULL mnemonic,
+  McBip39Language language,
+  const char* MC_NULLABLE passphrase,
+  const McAccountKeyFogInfo* MC_NULLABLE fog_info,
+  uint32_t gap_limit,
+  uint32_t max_account_count,
+  McSlip10AccountActivityCallback* MC_NONNULL callback,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 7);
+
+void mc_slip10_discovered_accounts_free(
+  McSlip10DiscoveredAccounts* MC_NULLABLE discovered_accounts
+);
+
+size_t mc_slip10_discovered_accounts_get_count(
+  const McSlip10DiscoveredAccounts* MC_NONNULL discovered_accounts
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the discovered account count.
+uint32_t mc_slip10_discovered_accounts_get_account_index(
+  const McSlip10DiscoveredAccounts* MC_NONNULL discovered_accounts,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
 #ifdef __cplusplus
 }
 #endif
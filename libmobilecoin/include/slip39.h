@@ -0,0 +1,101 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+#ifndef SLIP39_H_
+#define SLIP39_H_
+
+#include "common.h"
+
+/* ==================== SLIP39 ==================== */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ==== Types ==== */
+
+typedef struct _McSlip39Shares McSlip39Shares;
+
+/* ==== McSlip39Shares ==== */
+
+McSlip39Shares* MC_NULLABLE mc_slip39_shares_create();
+
+void mc_slip39_shares_free(
+  McSlip39Shares* MC_NULLABLE shares
+);
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+bool mc_slip39_shares_add(
+  McSlip39Shares* MC_NONNULL shares,
+  const char* MC_NONNULL mnemonic
+)
+MC_ATTRIBUTE_NONNULL(1, 2);
+
+size_t mc_slip39_shares_get_count(
+  const McSlip39Shares* MC_NONNULL shares
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `index` - must be less than the share count.
+char* MC_NULLABLE mc_slip39_shares_get_share(
+  const McSlip39Shares* MC_NONNULL shares,
+  size_t index
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/* ==== McSlip39 ==== */
+
+/// Splits `master_secret` into `share_count` mnemonic shares, any `threshold`
+/// of which recover it. `passphrase` encrypts the master secret and must be
+/// supplied again to `mc_slip39_combine_shares`. A null passphrase is treated
+/// as empty.
+///
+/// The share values and split identifier are drawn from the operating
+/// system's CSPRNG rather than an `McRngCallback`: `sssmc39` draws them from
+/// `rand::thread_rng()` internally and does not accept a caller-supplied RNG.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `master_secret` is shorter than 16 bytes or
+///   has an odd length, or `threshold` is 0 or greater than `share_count`.
+McSlip39Shares* MC_NULLABLE mc_slip39_split_secret(
+  const McSecret* MC_NONNULL master_secret,
+  const char* MC_NULLABLE passphrase,
+  uint8_t threshold,
+  uint8_t share_count,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// Recovers the master secret from `shares`, which must contain at least the
+/// threshold number of shares from a single split.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - a share is malformed or fails its checksum,
+///   the shares come from different splits, or there are too few of them.
+McSecret* MC_NULLABLE mc_slip39_combine_shares(
+  const McSlip39Shares* MC_NONNULL shares,
+  const char* MC_NULLABLE passphrase,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* !SLIP39_H_ */
@@ -25,7 +25,6 @@ typedef struct {
   uint64_t token_id;
 } McTxOutAmount;
 
-typedef struct _McTransactionBuilderRing McTransactionBuilderRing;
 typedef struct _McTransactionBuilder McTransactionBuilder;
 typedef struct _McTxOutMemoBuilder McTxOutMemoBuilder;
 
@@ -92,6 +91,21 @@ bool mc_tx_out_matches_subaddress(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 4, 5);
 
+/// Like `mc_tx_out_matches_subaddress`, but takes the private keys as secrets.
+///
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+bool mc_tx_out_matches_subaddress_with_secrets(
+  const McBuffer* MC_NONNULL tx_out_target_key,
+  const McBuffer* MC_NONNULL tx_out_public_key,
+  const McSecret* MC_NONNULL view_private_key,
+  const McSecret* MC_NONNULL subaddress_spend_private_key,
+  bool* MC_NONNULL out_matches
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 4, 5);
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -146,6 +160,28 @@ bool mc_tx_out_get_key_image(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 4, 5);
 
+/// Like `mc_tx_out_get_key_image`, but takes the private keys as secrets.
+///
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `out_key_image` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+bool mc_tx_out_get_key_image_with_secrets(
+  const McBuffer* MC_NONNULL tx_out_target_key,
+  const McBuffer* MC_NONNULL tx_out_public_key,
+  const McSecret* MC_NONNULL view_private_key,
+  const McSecret* MC_NONNULL subaddress_spend_private_key,
+  McMutableBuffer* MC_NONNULL out_key_image,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 4, 5);
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -179,6 +215,11 @@ MC_ATTRIBUTE_NONNULL(1, 2, 3);
 
 /* ==== McTransactionBuilder ==== */
 
+/// `current_block_height` is the height of the ledger as last seen by the
+/// app. Each output added to the builder is checked against the fog pubkey
+/// expiry of its recipient: an output whose recipient's fog pubkey expires at
+/// or before `current_block_height` is rejected, and otherwise the tombstone
+/// block is capped at that expiry so that fog can index the output.
 ///
 /// # Errors
 ///
@@ -187,12 +228,13 @@ McTransactionBuilder* MC_NULLABLE mc_transaction_builder_create(
   uint64_t fee,
   uint64_t token_id,
   uint64_t tombstone_block,
+  uint64_t current_block_height,
   const McFogResolver* MC_NULLABLE fog_resolver,
   McTxOutMemoBuilder* MC_NONNULL memo_builder,
   uint32_t block_version,
   McError* MC_NULLABLE * MC_NULLABLE out_error
 )
-MC_ATTRIBUTE_NONNULL(5);
+MC_ATTRIBUTE_NONNULL(6);
 
 void mc_transaction_builder_free(
   McTransactionBuilder* MC_NULLABLE transaction_builder
@@ -219,6 +261,30 @@ bool mc_transaction_builder_add_input(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
 
+/// Like `mc_transaction_builder_add_input`, but takes the private keys as
+/// secrets.
+///
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call to `build`.
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `real_index` - must be within bounds of `ring`.
+/// * `ring` - `TxOut` at `real_index` must be owned by account keys.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_transaction_builder_add_input_with_secrets(
+  McTransactionBuilder* MC_NONNULL transaction_builder,
+  const McSecret* MC_NONNULL view_private_key,
+  const McSecret* MC_NONNULL subaddress_spend_private_key,
+  size_t real_index,
+  const McTransactionBuilderRing* MC_NONNULL ring,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
+
 /// # Preconditions
 ///
 /// * `transaction_builder` - must not have been previously consumed by a call to `build`.
@@ -228,6 +294,9 @@ MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 McData* MC_NULLABLE mc_transaction_builder_add_output(
   McTransactionBuilder* MC_NONNULL transaction_builder,
@@ -250,6 +319,9 @@ MC_ATTRIBUTE_NONNULL(1, 3, 5, 6);
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 McData* MC_NULLABLE mc_transaction_builder_add_change_output(
   const McAccountKey* MC_NONNULL account_key,
@@ -262,6 +334,30 @@ McData* MC_NULLABLE mc_transaction_builder_add_change_output(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 4, 6);
 
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call
+///   to `build`.
+/// * `out_tx_out_confirmation_number` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
+/// * `LibMcError::InvalidInput`
+McData* MC_NULLABLE mc_transaction_builder_add_change_output_with_handle(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  McTransactionBuilder* MC_NONNULL transaction_builder,
+  uint64_t amount,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McMutableBuffer* MC_NONNULL out_tx_out_confirmation_number,
+  McMutableBuffer* MC_NONNULL out_tx_out_shared_secret,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 5, 6);
+
 /// # Preconditions
 ///
 /// * `account_key` - must be a valid account key as the gift code subaddress
@@ -273,6 +369,9 @@ MC_ATTRIBUTE_NONNULL(1, 2, 4, 6);
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 McData* MC_NULLABLE mc_transaction_builder_fund_gift_code_output(
         const McAccountKey* MC_NONNULL account_key,
@@ -284,6 +383,29 @@ McData* MC_NULLABLE mc_transaction_builder_fund_gift_code_output(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 5);
 
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call
+///   to `build`.
+/// * `out_tx_out_confirmation_number` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
+/// * `LibMcError::InvalidInput`
+McData* MC_NULLABLE mc_transaction_builder_fund_gift_code_output_with_handle(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  McTransactionBuilder* MC_NONNULL transaction_builder,
+  uint64_t amount,
+  McRngCallback* MC_NULLABLE rng_callback,
+  McMutableBuffer* MC_NONNULL out_tx_out_confirmation_number,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 5);
+
 /// # Preconditions
 ///
 /// * `transaction_builder` - must not have been previously consumed by a call to `build`.
@@ -298,7 +420,6 @@ McData* MC_NULLABLE mc_transaction_builder_build(
 )
 MC_ATTRIBUTE_NONNULL(1);
 
-
 /// # Preconditions
 ///
 /// * `account_key` - must be a valid `AccountKey` with `fog_info`.
@@ -315,6 +436,22 @@ McTxOutMemoBuilder* MC_NULLABLE mc_memo_builder_sender_payment_request_and_desti
 )
 MC_ATTRIBUTE_NONNULL(2);
 
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+McTxOutMemoBuilder* MC_NULLABLE mc_memo_builder_sender_and_destination_create_with_handle(
+  const McAccountKeyHandle* MC_NONNULL account_key_handle)
+MC_ATTRIBUTE_NONNULL(1);
+
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+McTxOutMemoBuilder* MC_NULLABLE mc_memo_builder_sender_payment_request_and_destination_create_with_handle(
+  uint64_t payment_request_id,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle
+)
+MC_ATTRIBUTE_NONNULL(2);
+
 McTxOutMemoBuilder* MC_NULLABLE mc_memo_builder_default_create();
 
 
@@ -366,6 +503,25 @@ bool mc_memo_sender_memo_create(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 4);
 
+/// # Preconditions
+///
+/// * `recipient_subaddress_view_public_key` - must be a valid
+///     32-byte Ristretto-format scalar.
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_data` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_memo_sender_memo_create_with_handle(
+  const McAccountKeyHandle* MC_NONNULL sender_account_key_handle,
+  const McBuffer* MC_NONNULL recipient_subaddress_view_public_key,
+  const McBuffer* MC_NONNULL tx_out_public_key,
+  McMutableBuffer* MC_NONNULL out_memo_data,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 4);
+
 /// # Preconditions
 ///
 /// * `sender_memo_data` - must be 64 bytes
@@ -507,6 +663,26 @@ bool mc_memo_sender_with_payment_request_memo_create(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
 
+/// # Preconditions
+///
+/// * `recipient_subaddress_view_public_key` - must be a valid
+///     32-byte Ristretto-format scalar.
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_data` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_memo_sender_with_payment_request_memo_create_with_handle(
+  const McAccountKeyHandle* MC_NONNULL sender_account_key_handle,
+  const McBuffer* MC_NONNULL recipient_subaddress_view_public_key,
+  const McBuffer* MC_NONNULL tx_out_public_key,
+  uint64_t payment_request_id,
+  McMutableBuffer* MC_NONNULL out_memo_data,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 5);
+
 /// # Preconditions
 ///
 /// * `sender_with_payment_request_memo_data` - must be 64 bytes
@@ -752,6 +928,24 @@ bool mc_memo_decrypt_e_memo_payload(
 )
 MC_ATTRIBUTE_NONNULL(1, 2, 3, 4);
 
+/// # Preconditions
+///
+/// * `encrypted_memo` - must be 66 bytes
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_payload` - length must be >= 16 bytes
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+bool mc_memo_decrypt_e_memo_payload_with_handle(
+  const McBuffer* MC_NONNULL encrypted_memo,
+  const McBuffer* MC_NONNULL tx_out_public_key,
+  const McAccountKeyHandle* MC_NONNULL account_key_handle,
+  McMutableBuffer* MC_NONNULL out_memo_payload,
+  McError* MC_NULLABLE * MC_NULLABLE out_error
+)
+MC_ATTRIBUTE_NONNULL(1, 2, 3, 4);
+
 
 #ifdef __cplusplus
 }
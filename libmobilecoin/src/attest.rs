@@ -2,7 +2,7 @@
 
 use crate::{common::*, LibMcError};
 use aes_gcm::Aes256Gcm;
-use core::str::FromStr;
+use core::{ops::Deref, str::FromStr};
 use libc::ssize_t;
 use mc_attest_ake::{
     AuthPending, AuthResponseInput, AuthResponseOutput, ClientInitiate, Ready, Start, Transition,
@@ -15,6 +15,7 @@ use mc_crypto_noise::NoiseCipher;
 use mc_crypto_rand::McRng;
 use mc_util_ffi::*;
 use sha2::Sha512;
+use std::os::raw::c_int;
 
 pub type McMrEnclaveVerifier = MrEnclaveVerifier;
 impl_into_ffi!(MrEnclaveVerifier);
@@ -158,20 +159,99 @@ pub extern "C" fn mc_mr_signer_verifier_allow_hardening_advisory(
     })
 }
 
-pub type McVerifier = Verifier;
-impl_into_ffi!(Verifier);
+pub type McDebugEnclavePolicy = c_int;
+
+/// Only production enclaves are accepted.
+pub const MC_DEBUG_ENCLAVE_POLICY_PRODUCTION_ONLY: McDebugEnclavePolicy = 0;
+/// Debug enclaves are accepted as well, e.g. for test networks.
+pub const MC_DEBUG_ENCLAVE_POLICY_ALLOW_DEBUG: McDebugEnclavePolicy = 1;
+
+/// A `Verifier` along with the debug enclave policy it was built with, which
+/// `Verifier` does not expose.
+pub struct AttestVerifier {
+    verifier: Verifier,
+    allow_debug: bool,
+}
+
+impl AttestVerifier {
+    fn new(allow_debug: bool) -> Self {
+        let mut verifier = Verifier::default();
+        verifier.debug(allow_debug);
+        Self {
+            verifier,
+            allow_debug,
+        }
+    }
+
+    fn with_debug_enclave_policy(
+        debug_enclave_policy: McDebugEnclavePolicy,
+    ) -> Result<Self, LibMcError> {
+        match debug_enclave_policy {
+            MC_DEBUG_ENCLAVE_POLICY_PRODUCTION_ONLY => Ok(Self::new(false)),
+            MC_DEBUG_ENCLAVE_POLICY_ALLOW_DEBUG => Ok(Self::new(true)),
+            _ => Err(LibMcError::InvalidInput(format!(
+                "Unknown debug enclave policy: {}",
+                debug_enclave_policy
+            ))),
+        }
+    }
+
+    fn debug_enclave_policy(&self) -> McDebugEnclavePolicy {
+        if self.allow_debug {
+            MC_DEBUG_ENCLAVE_POLICY_ALLOW_DEBUG
+        } else {
+            MC_DEBUG_ENCLAVE_POLICY_PRODUCTION_ONLY
+        }
+    }
+}
+
+impl Deref for AttestVerifier {
+    type Target = Verifier;
+
+    fn deref(&self) -> &Verifier {
+        &self.verifier
+    }
+}
+
+pub type McVerifier = AttestVerifier;
+impl_into_ffi!(AttestVerifier);
 
 /// Construct a new builder using the baked-in IAS root certificates and debug
 /// settings.
 #[no_mangle]
 pub extern "C" fn mc_verifier_create() -> FfiOptOwnedPtr<McVerifier> {
-    ffi_boundary(|| {
-        let mut verifier = Verifier::default();
-        verifier.debug(DEBUG_ENCLAVE);
-        verifier
+    ffi_boundary(|| AttestVerifier::new(DEBUG_ENCLAVE))
+}
+
+/// Construct a new builder using the baked-in IAS root certificates, accepting
+/// debug enclaves only if `debug_enclave_policy` allows it, regardless of the
+/// build settings.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `debug_enclave_policy` is not a known
+///   policy.
+#[no_mangle]
+pub extern "C" fn mc_verifier_create_with_debug_enclave_policy(
+    debug_enclave_policy: McDebugEnclavePolicy,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McVerifier> {
+    ffi_boundary_with_error(out_error, || {
+        AttestVerifier::with_debug_enclave_policy(debug_enclave_policy)
     })
 }
 
+/// Returns the debug enclave policy the verifier was constructed with.
+///
+/// A panic is reported as 0, i.e. `MC_DEBUG_ENCLAVE_POLICY_PRODUCTION_ONLY`,
+/// the same value as a verifier that rejects debug enclaves.
+#[no_mangle]
+pub extern "C" fn mc_verifier_get_debug_enclave_policy(
+    verifier: FfiRefPtr<McVerifier>,
+) -> McDebugEnclavePolicy {
+    ffi_boundary(|| verifier.debug_enclave_policy())
+}
+
 #[no_mangle]
 pub extern "C" fn mc_verifier_free(verifier: FfiOptOwnedPtr<McVerifier>) {
     ffi_boundary(|| {
@@ -188,6 +268,7 @@ pub extern "C" fn mc_verifier_add_mr_enclave(
     ffi_boundary(|| {
         verifier
             .into_mut()
+            .verifier
             .mr_enclave((*mr_enclave_verifier).clone());
     })
 }
@@ -199,7 +280,10 @@ pub extern "C" fn mc_verifier_add_mr_signer(
     mr_signer_verifier: FfiRefPtr<McMrSignerVerifier>,
 ) -> bool {
     ffi_boundary(|| {
-        verifier.into_mut().mr_signer((*mr_signer_verifier).clone());
+        verifier
+            .into_mut()
+            .verifier
+            .mr_signer((*mr_signer_verifier).clone());
     })
 }
 
@@ -359,7 +443,8 @@ pub extern "C" fn mc_attest_ake_process_auth_response(
             .expect("attest_ake is not in the auth pending state");
 
         let auth_response_output = AuthResponseOutput::from(auth_response_data.to_vec());
-        let auth_response_input = AuthResponseInput::new(auth_response_output, (*verifier).clone());
+        let auth_response_input =
+            AuthResponseInput::new(auth_response_output, verifier.verifier.clone());
         let mut rng = McRng::default(); // This is actually unused.
         let (ready, _) = auth_pending.try_next(&mut rng, auth_response_input)?;
         *attest_ake = AttestAke::Attested(ready);
@@ -470,3 +555,27 @@ impl<'a> TryFromFfi<FfiStr<'a>> for ResponderId {
             .map_err(|err| LibMcError::InvalidInput(format!("Invalid responder id: {:?}", err)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_enclave_policy_round_trips() {
+        for debug_enclave_policy in [
+            MC_DEBUG_ENCLAVE_POLICY_PRODUCTION_ONLY,
+            MC_DEBUG_ENCLAVE_POLICY_ALLOW_DEBUG,
+        ] {
+            let verifier = AttestVerifier::with_debug_enclave_policy(debug_enclave_policy).unwrap();
+            assert_eq!(verifier.debug_enclave_policy(), debug_enclave_policy);
+        }
+    }
+
+    #[test]
+    fn unknown_debug_enclave_policies_are_rejected() {
+        assert!(matches!(
+            AttestVerifier::with_debug_enclave_policy(2),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,567 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! Passphrase-encrypted account backups.
+//!
+//! A backup is a fixed-size header followed by an AES-256-GCM ciphertext:
+//!
+//! | Field   | Size | Notes                               |
+//! |---------|------|-------------------------------------|
+//! | magic   | 4    | `MCAB`                              |
+//! | version | 1    | currently `1`                       |
+//! | log_n   | 1    | scrypt cost, as a power of two      |
+//! | r       | 4    | scrypt block size, big-endian       |
+//! | p       | 4    | scrypt parallelism, big-endian      |
+//! | salt    | 16   | scrypt salt                         |
+//! | nonce   | 12   | AES-GCM nonce                       |
+//!
+//! The encryption key is derived from the passphrase with scrypt, and the
+//! whole header is authenticated as associated data so that neither the
+//! version nor the KDF parameters can be altered without opening failing.
+
+use crate::{bip39::is_valid_bip39_entropy_len, common::*, keys::McAccountKeyFogInfo, LibMcError};
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use core::convert::TryFrom;
+use mc_util_ffi::*;
+use rand_core::RngCore;
+use std::os::raw::c_int;
+use zeroize::Zeroizing;
+
+/* ==== McAccountBackupEntropyType ==== */
+
+pub type McAccountBackupEntropyType = c_int;
+
+/// 32 bytes of `RootIdentity` entropy.
+pub const MC_ACCOUNT_BACKUP_ENTROPY_TYPE_ROOT_ENTROPY: McAccountBackupEntropyType = 0;
+/// BIP39 mnemonic entropy, from which the account key is derived via SLIP-0010.
+pub const MC_ACCOUNT_BACKUP_ENTROPY_TYPE_BIP39_ENTROPY: McAccountBackupEntropyType = 1;
+
+/* ==== McAccountBackup ==== */
+
+#[repr(C)]
+pub struct McAccountBackup<'a> {
+    pub entropy_type: McAccountBackupEntropyType,
+    pub entropy: FfiRefPtr<'a, McSecret>,
+    pub fog_info: FfiOptRefPtr<'a, McAccountKeyFogInfo<'a>>,
+}
+
+const BACKUP_MAGIC: [u8; 4] = *b"MCAB";
+const BACKUP_VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// scrypt parameters used for new backups: 2^15 rounds over 1 KiB blocks, or
+/// 32 MiB of memory, which is still practical on older phones.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Upper bound on the memory scrypt may use when opening a backup, i.e.
+/// `128 * r * 2^log_n` bytes, so that a crafted header cannot make the KDF
+/// exhaust a phone's memory. This allows `log_n` up to 16 with `r = 8`.
+const MAX_SCRYPT_MEMORY: u64 = 64 * 1024 * 1024;
+/// Upper bound on scrypt parallelism when opening a backup. The work is
+/// repeated `p` times, so this bounds the time spent in the KDF together with
+/// `MAX_SCRYPT_MEMORY`.
+const MAX_SCRYPT_P: u32 = 4;
+
+struct BackupHeader {
+    version: u8,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+}
+
+impl BackupHeader {
+    fn generate(rng: &mut impl RngCore) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        Self {
+            version: BACKUP_VERSION,
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt,
+            nonce,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BACKUP_MAGIC.len() + 10 + SALT_LEN + NONCE_LEN);
+        bytes.extend_from_slice(&BACKUP_MAGIC);
+        bytes.push(self.version);
+        bytes.push(self.log_n);
+        bytes.extend_from_slice(&self.r.to_be_bytes());
+        bytes.extend_from_slice(&self.p.to_be_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, LibMcError> {
+        if reader.take(BACKUP_MAGIC.len())? != BACKUP_MAGIC {
+            return Err(LibMcError::InvalidInput(
+                "Bytes are not an account backup".to_owned(),
+            ));
+        }
+        let version = reader.read_u8()?;
+        if version != BACKUP_VERSION {
+            return Err(LibMcError::UnsupportedBackupVersion(version.to_string()));
+        }
+        let log_n = reader.read_u8()?;
+        let r = reader.read_u32()?;
+        let p = reader.read_u32()?;
+        let memory = 1u64
+            .checked_shl(log_n.into())
+            .and_then(|n| n.checked_mul(128 * u64::from(r)));
+        if !matches!(memory, Some(memory) if memory <= MAX_SCRYPT_MEMORY) || p > MAX_SCRYPT_P {
+            return Err(LibMcError::InvalidInput(format!(
+                "scrypt parameters exceed the supported maximum: log_n={}, r={}, p={}",
+                log_n, r, p
+            )));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(reader.take(SALT_LEN)?);
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(reader.take(NONCE_LEN)?);
+        Ok(Self {
+            version,
+            log_n,
+            r,
+            p,
+            salt,
+            nonce,
+        })
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LEN]>, LibMcError> {
+        let params = scrypt::Params::new(self.log_n, self.r, self.p).map_err(|err| {
+            LibMcError::InvalidInput(format!("Invalid scrypt parameters: {}", err))
+        })?;
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        scrypt::scrypt(passphrase.as_bytes(), &self.salt, &params, &mut key[..])
+            .map_err(|err| LibMcError::InvalidInput(format!("scrypt failed: {}", err)))?;
+        Ok(key)
+    }
+}
+
+struct BackupFogInfo {
+    report_url: String,
+    report_id: String,
+    authority_spki: Vec<u8>,
+}
+
+/// The plaintext of a backup. Each variable-length field is prefixed with its
+/// length as a big-endian `u16`.
+struct BackupContents {
+    entropy_type: McAccountBackupEntropyType,
+    entropy: Zeroizing<Vec<u8>>,
+    fog_info: Option<BackupFogInfo>,
+}
+
+impl BackupContents {
+    fn encode(&self) -> Result<Zeroizing<Vec<u8>>, LibMcError> {
+        let fog_fields: Vec<&[u8]> = self.fog_info.as_ref().map_or_else(Vec::new, |fog_info| {
+            vec![
+                fog_info.report_url.as_bytes(),
+                fog_info.report_id.as_bytes(),
+                &fog_info.authority_spki,
+            ]
+        });
+        // Reserve the exact length up front so the entropy is never left behind
+        // in a reallocated buffer.
+        let len = 2
+            + 2
+            + self.entropy.len()
+            + fog_fields
+                .iter()
+                .map(|field| 2 + field.len())
+                .sum::<usize>();
+        let mut bytes = Zeroizing::new(Vec::with_capacity(len));
+
+        bytes.push(
+            u8::try_from(self.entropy_type).expect("entropy_type is not a valid entropy type"),
+        );
+        write_field(&mut bytes, &self.entropy)?;
+        bytes.push(u8::from(self.fog_info.is_some()));
+        for field in fog_fields {
+            write_field(&mut bytes, field)?;
+        }
+        Ok(bytes)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, LibMcError> {
+        let mut reader = Reader(bytes);
+        let entropy_type = McAccountBackupEntropyType::from(reader.read_u8()?);
+        let entropy = Zeroizing::new(reader.read_field()?.to_vec());
+        validate_entropy(entropy_type, &entropy)?;
+        let fog_info = match reader.read_u8()? {
+            0 => None,
+            1 => Some(BackupFogInfo {
+                report_url: reader.read_str_field()?,
+                report_id: reader.read_str_field()?,
+                authority_spki: reader.read_field()?.to_vec(),
+            }),
+            flag => {
+                return Err(LibMcError::InvalidInput(format!(
+                    "Invalid fog info flag in backup: {}",
+                    flag
+                )))
+            }
+        };
+        if !reader.0.is_empty() {
+            return Err(LibMcError::InvalidInput(
+                "Trailing bytes in backup contents".to_owned(),
+            ));
+        }
+        Ok(Self {
+            entropy_type,
+            entropy,
+            fog_info,
+        })
+    }
+}
+
+fn validate_entropy(
+    entropy_type: McAccountBackupEntropyType,
+    entropy: &[u8],
+) -> Result<(), LibMcError> {
+    let valid = match entropy_type {
+        MC_ACCOUNT_BACKUP_ENTROPY_TYPE_ROOT_ENTROPY => entropy.len() == 32,
+        MC_ACCOUNT_BACKUP_ENTROPY_TYPE_BIP39_ENTROPY => is_valid_bip39_entropy_len(entropy.len()),
+        _ => {
+            return Err(LibMcError::InvalidInput(format!(
+                "Unknown entropy type: {}",
+                entropy_type
+            )))
+        }
+    };
+    if !valid {
+        return Err(LibMcError::InvalidInput(format!(
+            "Invalid entropy length for entropy type {}: {}",
+            entropy_type,
+            entropy.len()
+        )));
+    }
+    Ok(())
+}
+
+fn write_field(bytes: &mut Vec<u8>, field: &[u8]) -> Result<(), LibMcError> {
+    let len = u16::try_from(field.len()).map_err(|_| {
+        LibMcError::InvalidInput(format!("Backup field is too long: {} bytes", field.len()))
+    })?;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(field);
+    Ok(())
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], LibMcError> {
+        if self.0.len() < len {
+            return Err(LibMcError::InvalidInput(
+                "Account backup is truncated".to_owned(),
+            ));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, LibMcError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, LibMcError> {
+        let bytes = <[u8; 4]>::try_from(self.take(4)?).expect("take returned the wrong length");
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    fn read_field(&mut self) -> Result<&'a [u8], LibMcError> {
+        let len = <[u8; 2]>::try_from(self.take(2)?).expect("take returned the wrong length");
+        self.take(u16::from_be_bytes(len) as usize)
+    }
+
+    fn read_str_field(&mut self) -> Result<String, LibMcError> {
+        let field = self.read_field()?;
+        String::from_utf8(field.to_vec())
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid UTF-8 in backup: {}", err)))
+    }
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> Aes256Gcm {
+    Aes256Gcm::new(Key::from_slice(key))
+}
+
+fn seal(
+    contents: &BackupContents,
+    passphrase: &str,
+    rng: &mut impl RngCore,
+) -> Result<Vec<u8>, LibMcError> {
+    let plaintext = contents.encode()?;
+
+    let header = BackupHeader::generate(rng);
+    let header_bytes = header.to_bytes();
+    let key = header.derive_key(passphrase)?;
+
+    let ciphertext = cipher(&key)
+        .encrypt(
+            Nonce::from_slice(&header.nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &header_bytes,
+            },
+        )
+        .map_err(|err| LibMcError::Aead(format!("{:?}", err)))?;
+
+    let mut backup_bytes = header_bytes;
+    backup_bytes.extend_from_slice(&ciphertext);
+    Ok(backup_bytes)
+}
+
+fn open(backup_bytes: &[u8], passphrase: &str) -> Result<BackupContents, LibMcError> {
+    let mut reader = Reader(backup_bytes);
+    let header = BackupHeader::read(&mut reader)?;
+    let header_bytes = header.to_bytes();
+    let key = header.derive_key(passphrase)?;
+
+    let plaintext = Zeroizing::new(
+        cipher(&key)
+            .decrypt(
+                Nonce::from_slice(&header.nonce),
+                Payload {
+                    msg: reader.0,
+                    aad: &header_bytes,
+                },
+            )
+            .map_err(|_| {
+                LibMcError::Aead("Wrong passphrase or corrupted account backup".to_owned())
+            })?,
+    );
+    BackupContents::decode(&plaintext)
+}
+
+/// Encrypts `backup` under `passphrase`. The result is safe to store with an
+/// untrusted party, to the extent that the passphrase is strong.
+///
+/// # Preconditions
+///
+/// * `backup.entropy_type` - must be a valid `McAccountBackupEntropyType`.
+/// * `backup.entropy` - must hold 32 bytes of root entropy, or 16, 20, 24, 28,
+///   or 32 bytes of BIP39 entropy, matching `backup.entropy_type`.
+/// * `passphrase` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+#[no_mangle]
+pub extern "C" fn mc_account_backup_create(
+    backup: FfiRefPtr<McAccountBackup>,
+    passphrase: FfiStr,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        let passphrase = <&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+        if passphrase.is_empty() {
+            return Err(LibMcError::InvalidInput(
+                "Passphrase must not be empty".to_owned(),
+            ));
+        }
+        validate_entropy(backup.entropy_type, &backup.entropy)?;
+        let fog_info = match backup.fog_info.as_option() {
+            Some(fog_info) => Some(BackupFogInfo {
+                report_url: <&str>::try_from_ffi(fog_info.report_url)?.to_owned(),
+                report_id: <&str>::try_from_ffi(fog_info.report_id)?.to_owned(),
+                authority_spki: fog_info.authority_spki.to_vec(),
+            }),
+            None => None,
+        };
+        let contents = BackupContents {
+            entropy_type: backup.entropy_type,
+            entropy: Zeroizing::new(backup.entropy.to_vec()),
+            fog_info,
+        };
+
+        let mut rng = SdkRng::from_ffi(rng_callback);
+        seal(&contents, passphrase, &mut rng)
+    })
+}
+
+/// Decrypts a backup created by `mc_account_backup_create`.
+///
+/// `out_fog_report_url`, `out_fog_report_id`, and `out_fog_authority_spki` are
+/// set to null when the backup was created without fog info.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::UnsupportedBackupVersion`
+/// * `LibMcError::Aead` - the passphrase is wrong or the backup was modified.
+#[no_mangle]
+pub extern "C" fn mc_account_backup_open(
+    backup_bytes: FfiRefPtr<McBuffer>,
+    passphrase: FfiStr,
+    out_entropy_type: FfiMutPtr<McAccountBackupEntropyType>,
+    out_entropy: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_fog_report_url: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_report_id: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_authority_spki: FfiOptMutPtr<FfiOptOwnedPtr<McData>>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let passphrase = <&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+
+        let contents = open(&backup_bytes, passphrase)?;
+
+        // Convert every output before writing any, so that the out-params are
+        // left untouched on failure.
+        let fog_info = contents.fog_info.as_ref();
+        let fog_report_url =
+            FfiOptOwnedStr::ffi_try_from(fog_info.map(|fog_info| fog_info.report_url.as_str()))?;
+        let fog_report_id =
+            FfiOptOwnedStr::ffi_try_from(fog_info.map(|fog_info| fog_info.report_id.as_str()))?;
+        let fog_authority_spki = fog_info.map_or_else(Default::default, |fog_info| {
+            FfiOwnedPtr::new(fog_info.authority_spki.clone()).into()
+        });
+
+        if let Some(out_fog_report_url) = out_fog_report_url.into_mut() {
+            *out_fog_report_url = fog_report_url;
+        }
+        if let Some(out_fog_report_id) = out_fog_report_id.into_mut() {
+            *out_fog_report_id = fog_report_id;
+        }
+        if let Some(out_fog_authority_spki) = out_fog_authority_spki.into_mut() {
+            *out_fog_authority_spki = fog_authority_spki;
+        }
+        *out_entropy_type.into_mut() = contents.entropy_type;
+        *out_entropy.into_mut() = FfiOwnedPtr::new(contents.entropy).into();
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn contents() -> BackupContents {
+        BackupContents {
+            entropy_type: MC_ACCOUNT_BACKUP_ENTROPY_TYPE_BIP39_ENTROPY,
+            entropy: Zeroizing::new((0u8..16).collect()),
+            fog_info: Some(BackupFogInfo {
+                report_url: "fog://fog.example.com".to_owned(),
+                report_id: "".to_owned(),
+                authority_spki: vec![1, 2, 3],
+            }),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let backup_bytes = seal(&contents(), "correct horse", &mut rng).unwrap();
+
+        let opened = open(&backup_bytes, "correct horse").unwrap();
+        assert_eq!(
+            opened.entropy_type,
+            MC_ACCOUNT_BACKUP_ENTROPY_TYPE_BIP39_ENTROPY
+        );
+        assert_eq!(*opened.entropy, (0u8..16).collect::<Vec<_>>());
+        let fog_info = opened.fog_info.unwrap();
+        assert_eq!(fog_info.report_url, "fog://fog.example.com");
+        assert_eq!(fog_info.report_id, "");
+        assert_eq!(fog_info.authority_spki, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_an_error() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let backup_bytes = seal(&contents(), "correct horse", &mut rng).unwrap();
+
+        assert!(matches!(
+            open(&backup_bytes, "battery staple"),
+            Err(LibMcError::Aead(_))
+        ));
+    }
+
+    #[test]
+    fn header_is_authenticated_as_associated_data() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let backup_bytes = seal(&contents(), "correct horse", &mut rng).unwrap();
+        let mut reader = Reader(&backup_bytes);
+        let header = BackupHeader::read(&mut reader).unwrap();
+        let key = header.derive_key("correct horse").unwrap();
+
+        // With the key and nonce fixed, only the associated data differs, so a
+        // failure here can only come from the header being authenticated.
+        let decrypt = |aad: &[u8]| {
+            cipher(&key).decrypt(
+                Nonce::from_slice(&header.nonce),
+                Payload { msg: reader.0, aad },
+            )
+        };
+        let mut header_bytes = header.to_bytes();
+        assert!(decrypt(&header_bytes).is_ok());
+        header_bytes[BACKUP_MAGIC.len()] ^= 1;
+        assert!(decrypt(&header_bytes).is_err());
+    }
+
+    fn header_with_params(log_n: u8, r: u32, p: u32) -> Vec<u8> {
+        BackupHeader {
+            version: BACKUP_VERSION,
+            log_n,
+            r,
+            p,
+            salt: [0; SALT_LEN],
+            nonce: [0; NONCE_LEN],
+        }
+        .to_bytes()
+    }
+
+    #[test]
+    fn over_limit_scrypt_parameters_are_rejected() {
+        for (log_n, r, p) in [(17, 8, 1), (16, 9, 1), (20, 16, 1), (16, 8, 5), (64, 8, 1)] {
+            let header = header_with_params(log_n, r, p);
+            assert!(
+                matches!(
+                    BackupHeader::read(&mut Reader(&header)),
+                    Err(LibMcError::InvalidInput(_))
+                ),
+                "log_n={}, r={}, p={} was accepted",
+                log_n,
+                r,
+                p
+            );
+        }
+
+        let header = header_with_params(16, 8, 4);
+        assert!(BackupHeader::read(&mut Reader(&header)).is_ok());
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let mut header = header_with_params(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P);
+        header[BACKUP_MAGIC.len()] = BACKUP_VERSION + 1;
+
+        assert!(matches!(
+            BackupHeader::read(&mut Reader(&header)),
+            Err(LibMcError::UnsupportedBackupVersion(_))
+        ));
+    }
+}
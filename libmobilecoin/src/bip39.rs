@@ -1,9 +1,88 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
 use crate::{common::*, LibMcError};
-use bip39::{Language, Mnemonic};
+use bip39::{Language, Mnemonic, MnemonicType};
 use libc::ssize_t;
 use mc_util_ffi::*;
+use rand_core::RngCore;
+use std::os::raw::c_int;
+use zeroize::Zeroizing;
+
+/* ==== McBip39Language ==== */
+
+pub type McBip39Language = c_int;
+
+pub const MC_BIP39_LANGUAGE_ENGLISH: McBip39Language = 0;
+pub const MC_BIP39_LANGUAGE_CHINESE_SIMPLIFIED: McBip39Language = 1;
+pub const MC_BIP39_LANGUAGE_CHINESE_TRADITIONAL: McBip39Language = 2;
+pub const MC_BIP39_LANGUAGE_FRENCH: McBip39Language = 3;
+pub const MC_BIP39_LANGUAGE_ITALIAN: McBip39Language = 4;
+pub const MC_BIP39_LANGUAGE_JAPANESE: McBip39Language = 5;
+pub const MC_BIP39_LANGUAGE_KOREAN: McBip39Language = 6;
+pub const MC_BIP39_LANGUAGE_SPANISH: McBip39Language = 7;
+
+/// Every wordlist supported by `tiny-bip39`, in the order they are tried when
+/// detecting the language of a phrase.
+const ALL_LANGUAGES: [(McBip39Language, Language); 8] = [
+    (MC_BIP39_LANGUAGE_ENGLISH, Language::English),
+    (
+        MC_BIP39_LANGUAGE_CHINESE_SIMPLIFIED,
+        Language::ChineseSimplified,
+    ),
+    (
+        MC_BIP39_LANGUAGE_CHINESE_TRADITIONAL,
+        Language::ChineseTraditional,
+    ),
+    (MC_BIP39_LANGUAGE_FRENCH, Language::French),
+    (MC_BIP39_LANGUAGE_ITALIAN, Language::Italian),
+    (MC_BIP39_LANGUAGE_JAPANESE, Language::Japanese),
+    (MC_BIP39_LANGUAGE_KOREAN, Language::Korean),
+    (MC_BIP39_LANGUAGE_SPANISH, Language::Spanish),
+];
+
+impl TryFromFfi<McBip39Language> for Language {
+    type Error = LibMcError;
+
+    fn try_from_ffi(src: McBip39Language) -> Result<Self, LibMcError> {
+        ALL_LANGUAGES
+            .iter()
+            .find(|(code, _)| *code == src)
+            .map(|(_, language)| *language)
+            .ok_or_else(|| LibMcError::InvalidInput(format!("Unknown BIP39 language: {}", src)))
+    }
+}
+
+/// Returns the language whose wordlist produces a valid mnemonic (including
+/// the checksum) from `phrase`.
+///
+/// Wordlists share some words, e.g. the two Chinese ones, so a phrase can be
+/// valid in more than one language. That is reported as an error rather than
+/// picking one, since the languages derive different keys from the phrase.
+pub(crate) fn detect_language(phrase: &str) -> Result<(McBip39Language, Language), LibMcError> {
+    let mut matches = ALL_LANGUAGES
+        .iter()
+        .filter(|(_, language)| Mnemonic::validate(phrase, *language).is_ok())
+        .copied();
+    let first = matches.next().ok_or_else(|| {
+        LibMcError::InvalidInput("Mnemonic is not valid in any BIP39 language".to_owned())
+    })?;
+    let others = matches.map(|(_, language)| language).collect::<Vec<_>>();
+    if !others.is_empty() {
+        return Err(LibMcError::AmbiguousInput(format!(
+            "Mnemonic is valid in more than one BIP39 language: {:?}, {:?}",
+            first.1, others
+        )));
+    }
+    Ok(first)
+}
+
+/* ==== McBip39 ==== */
+
+/// Whether `len` is a valid BIP39 entropy length in bytes: a multiple of 4
+/// between 16 and 32, inclusive.
+pub(crate) fn is_valid_bip39_entropy_len(len: usize) -> bool {
+    (16..=32).contains(&len) && len % 4 == 0
+}
 
 /// # Preconditions
 ///
@@ -11,14 +90,94 @@ use mc_util_ffi::*;
 ///   inclusive, in bytes.
 #[no_mangle]
 pub extern "C" fn mc_bip39_mnemonic_from_entropy(entropy: FfiRefPtr<McBuffer>) -> FfiOptOwnedStr {
+    mc_bip39_mnemonic_from_entropy_with_language(entropy, MC_BIP39_LANGUAGE_ENGLISH)
+}
+
+/// # Preconditions
+///
+/// * `entropy` - length must be a multiple of 4 and between 16 and 32,
+///   inclusive, in bytes.
+/// * `language` - must be a valid `McBip39Language`.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_from_entropy_with_language(
+    entropy: FfiRefPtr<McBuffer>,
+    language: McBip39Language,
+) -> FfiOptOwnedStr {
     ffi_boundary(|| {
-        let mnemonic = Mnemonic::from_entropy(&entropy, Language::English)
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+
+        let mnemonic = Mnemonic::from_entropy(&entropy, language)
             .expect("entropy could not be converted to a mnemonic");
         FfiOwnedStr::ffi_try_from(mnemonic.to_string())
             .expect("mnemonic could not be converted to a C string")
     })
 }
 
+/// Generates a new mnemonic of `word_count` words from fresh entropy. Uses
+/// `rng_callback` if provided, or else the library's own CSPRNG.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_generate(
+    word_count: usize,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedStr {
+    mc_bip39_mnemonic_generate_with_language(
+        word_count,
+        MC_BIP39_LANGUAGE_ENGLISH,
+        rng_callback,
+        out_error,
+    )
+}
+
+/// Generates a new mnemonic of `word_count` words from fresh entropy. Uses
+/// `rng_callback` if provided, or else the library's own CSPRNG.
+///
+/// # Preconditions
+///
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_generate_with_language(
+    word_count: usize,
+    language: McBip39Language,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedStr {
+    ffi_boundary_with_error(out_error, || {
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+        let mut rng = SdkRng::from_ffi(rng_callback);
+
+        let mnemonic = generate_mnemonic(word_count, language, &mut rng)?;
+        Ok(FfiOwnedStr::ffi_try_from(mnemonic.to_string())
+            .expect("mnemonic could not be converted to a C string"))
+    })
+}
+
+fn generate_mnemonic(
+    word_count: usize,
+    language: Language,
+    rng: &mut impl RngCore,
+) -> Result<Mnemonic, LibMcError> {
+    let mnemonic_type = MnemonicType::for_word_count(word_count).map_err(|_| {
+        LibMcError::InvalidInput(format!(
+            "Word count must be 12, 15, 18, 21 or 24, got {}",
+            word_count
+        ))
+    })?;
+
+    let mut entropy = Zeroizing::new(vec![0u8; mnemonic_type.entropy_bits() / 8]);
+    rng.fill_bytes(&mut entropy);
+    Ok(Mnemonic::from_entropy(&entropy, language)
+        .expect("entropy could not be converted to a mnemonic"))
+}
+
 /// # Preconditions
 ///
 /// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
@@ -32,11 +191,36 @@ pub extern "C" fn mc_bip39_entropy_from_mnemonic(
     mnemonic: FfiStr,
     out_entropy: FfiOptMutPtr<McMutableBuffer>,
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> ssize_t {
+    mc_bip39_entropy_from_mnemonic_with_language(
+        mnemonic,
+        MC_BIP39_LANGUAGE_ENGLISH,
+        out_entropy,
+        out_error,
+    )
+}
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `out_entropy` - must be null or else length must be >= `entropy.len`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_bip39_entropy_from_mnemonic_with_language(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    out_entropy: FfiOptMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> ssize_t {
     ffi_boundary_with_error(out_error, || {
         let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
 
-        let mnemonic = Mnemonic::from_phrase(mnemonic, Language::English)
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
             .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
         let entropy = mnemonic.entropy();
 
@@ -52,16 +236,396 @@ pub extern "C" fn mc_bip39_entropy_from_mnemonic(
     })
 }
 
+/// Like `mc_bip39_entropy_from_mnemonic_with_language`, but the entropy is
+/// returned as a secret rather than copied into foreign memory.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_bip39_entropy_from_mnemonic_to_secret(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McSecret> {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
+        Ok(Zeroizing::new(mnemonic.entropy().to_vec()))
+    })
+}
+
+/// Finds the wordlist in which `mnemonic` is a valid phrase, checksum
+/// included.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AmbiguousInput` - `mnemonic` is valid in more than one
+///   language.
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_detect_language(
+    mnemonic: FfiStr,
+    out_language: FfiMutPtr<McBip39Language>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+
+        let (language, _) = detect_language(mnemonic)?;
+        *out_language.into_mut() = language;
+        Ok(())
+    })
+}
+
 /// # Preconditions
 ///
 /// * `prefix` - must be a nul-terminated C string containing valid UTF-8.
 #[no_mangle]
 pub extern "C" fn mc_bip39_words_by_prefix(prefix: FfiStr) -> FfiOptOwnedStr {
+    mc_bip39_words_by_prefix_with_language(prefix, MC_BIP39_LANGUAGE_ENGLISH)
+}
+
+/// # Preconditions
+///
+/// * `prefix` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+#[no_mangle]
+pub extern "C" fn mc_bip39_words_by_prefix_with_language(
+    prefix: FfiStr,
+    language: McBip39Language,
+) -> FfiOptOwnedStr {
     ffi_boundary(|| {
         let prefix = <&str>::try_from_ffi(prefix).expect("prefix is invalid");
-        let words = Language::English.wordlist().get_words_by_prefix(prefix);
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+
+        let words = language.wordlist().get_words_by_prefix(prefix);
         let joined_words = words.join(",");
         FfiOwnedStr::ffi_try_from(joined_words)
             .expect("joined_words could not be converted to a C string")
     })
 }
+
+/* ==== McBip39MnemonicValidation ==== */
+
+/// Maximum edit distance for a wordlist entry to be suggested in place of an
+/// unknown word.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+/// Maximum number of suggestions reported for each unknown word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// A word of a phrase that does not appear in the wordlist.
+pub struct UnknownWord {
+    /// Zero-based position of the word in the phrase.
+    position: usize,
+    /// Closest wordlist entries, nearest first.
+    suggestions: Vec<&'static str>,
+}
+
+/// Why a phrase is or is not a valid mnemonic, in enough detail to point the
+/// user at the words that need fixing.
+pub struct MnemonicValidation {
+    word_count: usize,
+    unknown_words: Vec<UnknownWord>,
+    /// Only meaningful when the word count is valid and every word is known.
+    checksum_valid: bool,
+}
+
+impl MnemonicValidation {
+    pub fn new(phrase: &str, language: Language) -> Self {
+        let wordlist = language.wordlist().get_words_by_prefix("");
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+
+        let unknown_words = words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| !wordlist.iter().any(|entry| entry == *word))
+            .map(|(position, word)| UnknownWord {
+                position,
+                suggestions: closest_words(word, wordlist),
+            })
+            .collect::<Vec<_>>();
+
+        let word_count_valid = MnemonicType::for_word_count(words.len()).is_ok();
+        let checksum_valid = word_count_valid
+            && unknown_words.is_empty()
+            && Mnemonic::validate(phrase, language).is_ok();
+
+        Self {
+            word_count: words.len(),
+            unknown_words,
+            checksum_valid,
+        }
+    }
+
+    pub fn is_word_count_valid(&self) -> bool {
+        MnemonicType::for_word_count(self.word_count).is_ok()
+    }
+
+    pub fn has_checksum_error(&self) -> bool {
+        self.is_word_count_valid() && self.unknown_words.is_empty() && !self.checksum_valid
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.checksum_valid
+    }
+
+    fn unknown_word(&self, index: usize) -> &UnknownWord {
+        self.unknown_words
+            .get(index)
+            .expect("index must be less than the unknown word count")
+    }
+}
+
+fn closest_words(word: &str, wordlist: &[&'static str]) -> Vec<&'static str> {
+    let mut candidates = wordlist
+        .iter()
+        .map(|candidate| (edit_distance(word, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect::<Vec<_>>();
+    // Stable sort, so equally close words keep their wordlist order.
+    candidates.sort_by_key(|(distance, _)| *distance);
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+pub type McBip39MnemonicValidation = MnemonicValidation;
+impl_into_ffi!(MnemonicValidation);
+
+/// Validates `mnemonic` word by word against the wordlist for `language`.
+/// Unlike `mc_bip39_entropy_from_mnemonic_with_language`, this does not stop
+/// at the first problem, so the result can describe every unknown word.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validate(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+) -> FfiOptOwnedPtr<McBip39MnemonicValidation> {
+    ffi_boundary(|| {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+
+        MnemonicValidation::new(mnemonic, language)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_free(
+    validation: FfiOptOwnedPtr<McBip39MnemonicValidation>,
+) {
+    ffi_boundary(|| {
+        let _ = validation;
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_is_valid(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+) -> bool {
+    ffi_boundary(|| validation.is_valid())
+}
+
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_get_word_count(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+) -> usize {
+    ffi_boundary(|| validation.word_count)
+}
+
+/// Whether the phrase has 12, 15, 18, 21 or 24 words.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_is_word_count_valid(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+) -> bool {
+    ffi_boundary(|| validation.is_word_count_valid())
+}
+
+/// Whether the phrase has a valid word count and only known words, but its
+/// checksum does not match, i.e. words are swapped or misremembered.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_has_checksum_error(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+) -> bool {
+    ffi_boundary(|| validation.has_checksum_error())
+}
+
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_get_unknown_word_count(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+) -> usize {
+    ffi_boundary(|| validation.unknown_words.len())
+}
+
+/// Returns the zero-based position in the phrase of the `index`th unknown
+/// word.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the unknown word count.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_get_unknown_word_position(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+    index: usize,
+) -> usize {
+    ffi_boundary(|| validation.unknown_word(index).position)
+}
+
+/// Returns the closest wordlist entries to the `index`th unknown word as a
+/// comma-separated list, nearest first. The list is empty when no entry is
+/// close enough to suggest.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the unknown word count.
+#[no_mangle]
+pub extern "C" fn mc_bip39_mnemonic_validation_get_unknown_word_suggestions(
+    validation: FfiRefPtr<McBip39MnemonicValidation>,
+    index: usize,
+) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        let joined_words = validation.unknown_word(index).suggestions.join(",");
+        FfiOwnedStr::ffi_try_from(joined_words)
+            .expect("joined_words could not be converted to a C string")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    const ENGLISH_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                                  abandon abandon abandon about";
+
+    #[test]
+    fn detects_the_language_of_a_phrase() {
+        let (language, _) = detect_language(ENGLISH_PHRASE).unwrap();
+        assert_eq!(language, MC_BIP39_LANGUAGE_ENGLISH);
+
+        let phrase = Mnemonic::from_entropy(&[0x5a; 16], Language::Korean)
+            .unwrap()
+            .to_string();
+        let (language, _) = detect_language(&phrase).unwrap();
+        assert_eq!(language, MC_BIP39_LANGUAGE_KOREAN);
+    }
+
+    #[test]
+    fn phrases_valid_in_several_languages_are_ambiguous() {
+        // The two Chinese wordlists start with the same words, so the phrase
+        // for all-zero entropy is valid in both.
+        let phrase = Mnemonic::from_entropy(&[0; 16], Language::ChineseSimplified)
+            .unwrap()
+            .to_string();
+        assert!(Mnemonic::validate(&phrase, Language::ChineseTraditional).is_ok());
+
+        assert!(matches!(
+            detect_language(&phrase),
+            Err(LibMcError::AmbiguousInput(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_phrases_have_no_language() {
+        assert!(matches!(
+            detect_language("abandon abandon abandon"),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn validation_accepts_a_valid_phrase() {
+        let validation = MnemonicValidation::new(ENGLISH_PHRASE, Language::English);
+        assert!(validation.is_valid());
+        assert!(validation.is_word_count_valid());
+        assert!(!validation.has_checksum_error());
+        assert!(validation.unknown_words.is_empty());
+    }
+
+    #[test]
+    fn validation_reports_checksum_errors() {
+        let phrase = ENGLISH_PHRASE.replace("about", "abandon");
+        let validation = MnemonicValidation::new(&phrase, Language::English);
+        assert!(!validation.is_valid());
+        assert!(validation.has_checksum_error());
+    }
+
+    #[test]
+    fn validation_reports_word_count_errors() {
+        let validation = MnemonicValidation::new("abandon abandon about", Language::English);
+        assert!(!validation.is_valid());
+        assert!(!validation.is_word_count_valid());
+        assert!(!validation.has_checksum_error());
+    }
+
+    #[test]
+    fn validation_suggests_close_words_for_unknown_words() {
+        let phrase = ENGLISH_PHRASE.replacen("abandon", "abandn", 1);
+        let validation = MnemonicValidation::new(&phrase, Language::English);
+        assert!(!validation.is_valid());
+        assert!(!validation.has_checksum_error());
+        assert_eq!(validation.unknown_words.len(), 1);
+
+        let unknown_word = validation.unknown_word(0);
+        assert_eq!(unknown_word.position, 0);
+        assert_eq!(unknown_word.suggestions[0], "abandon");
+        assert!(unknown_word.suggestions.len() <= MAX_SUGGESTIONS);
+    }
+
+    #[test]
+    fn edit_distance_counts_chars() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("的的", "的在"), 1);
+    }
+
+    #[test]
+    fn generated_mnemonics_have_the_requested_word_count() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for word_count in [12, 15, 18, 21, 24] {
+            let mnemonic = generate_mnemonic(word_count, Language::English, &mut rng).unwrap();
+            assert_eq!(mnemonic.phrase().split_whitespace().count(), word_count);
+        }
+        for word_count in [0, 11, 13, 25] {
+            assert!(matches!(
+                generate_mnemonic(word_count, Language::English, &mut rng),
+                Err(LibMcError::InvalidInput(_))
+            ));
+        }
+    }
+}
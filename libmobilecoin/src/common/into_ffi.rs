@@ -71,7 +71,7 @@ impl IntoFfi<bool> for () {
     }
 }
 
-impl_into_ffi_using_default![u64];
+impl_into_ffi_using_default![i32, u32, u64, usize];
 
 impl IntoFfi<i64> for i64 {
     #[inline]
@@ -43,6 +43,7 @@ mod data;
 mod error;
 mod into_ffi;
 mod rng;
+mod secret;
 mod string;
 
 pub use self::{
@@ -50,6 +51,7 @@ pub use self::{
     data::{mc_data_free, mc_data_get_bytes, McData},
     error::{mc_error_free, McError},
     rng::{CallbackRng, FfiCallbackRng, McRngCallback, SdkRng},
+    secret::{mc_secret_create, mc_secret_export_bytes, mc_secret_free, McSecret},
     string::mc_string_free,
 };
 
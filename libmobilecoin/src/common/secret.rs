@@ -0,0 +1,46 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use super::*;
+use libc::ssize_t;
+use mc_util_ffi::{FfiOptMutPtr, FfiOptOwnedPtr, FfiRefPtr};
+use zeroize::Zeroizing;
+
+/// Key material held in Rust-owned memory that is zeroized when the secret is
+/// freed. Unlike `McData`, the bytes are only handed back to foreign code by an
+/// explicit call to `mc_secret_export_bytes`.
+pub type McSecret = Zeroizing<Vec<u8>>;
+impl_into_ffi!(Zeroizing<Vec<u8>>);
+
+/// Copies `bytes` into a new secret. The caller remains responsible for
+/// clearing its own copy.
+#[no_mangle]
+pub extern "C" fn mc_secret_create(bytes: FfiRefPtr<McBuffer>) -> FfiOptOwnedPtr<McSecret> {
+    ffi_boundary(|| Zeroizing::new(bytes.to_vec()))
+}
+
+#[no_mangle]
+pub extern "C" fn mc_secret_free(secret: FfiOptOwnedPtr<McSecret>) {
+    ffi_boundary(|| {
+        let _ = secret;
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_bytes` - must be null or else length must be >= `secret.len`.
+#[no_mangle]
+pub extern "C" fn mc_secret_export_bytes(
+    secret: FfiRefPtr<McSecret>,
+    out_bytes: FfiOptMutPtr<McMutableBuffer>,
+) -> ssize_t {
+    ffi_boundary(|| {
+        if let Some(out_bytes) = out_bytes.into_option() {
+            out_bytes
+                .into_mut()
+                .as_slice_mut_of_len(secret.len())
+                .expect("out_bytes length is insufficient")
+                .copy_from_slice(&secret);
+        }
+        ssize_t::ffi_try_from(secret.len()).expect("secret.len could not be converted to ssize_t")
+    })
+}
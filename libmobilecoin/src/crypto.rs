@@ -30,6 +30,13 @@ impl<'a> TryFromFfi<&McBuffer<'a>> for RistrettoPrivate {
     }
 }
 
+pub(crate) fn ristretto_private_from_secret(
+    secret: &McSecret,
+) -> Result<RistrettoPrivate, LibMcError> {
+    RistrettoPrivate::try_from(secret.as_slice())
+        .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))
+}
+
 #[no_mangle]
 pub extern "C" fn mc_ristretto_private_validate(
     ristretto_private: FfiRefPtr<McBuffer>,
@@ -163,3 +170,34 @@ pub extern "C" fn mc_versioned_crypto_box_decrypt(
             .expect("plaintext.len could not be converted to ssize_t"))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+    use zeroize::Zeroizing;
+
+    #[test]
+    fn ristretto_private_round_trips_through_secret() {
+        let private_key = RistrettoPrivate::from_random(&mut ChaCha20Rng::seed_from_u64(0));
+        let bytes: &[u8] = private_key.as_ref();
+        let secret = Zeroizing::new(bytes.to_vec());
+
+        let decoded = ristretto_private_from_secret(&secret).unwrap();
+        assert_eq!(
+            RistrettoPublic::from(&decoded),
+            RistrettoPublic::from(&private_key)
+        );
+    }
+
+    #[test]
+    fn malformed_secrets_are_not_ristretto_privates() {
+        for bytes in [vec![], vec![1; 31], vec![1; 33], vec![0xff; 32]] {
+            assert!(matches!(
+                ristretto_private_from_secret(&Zeroizing::new(bytes)),
+                Err(LibMcError::InvalidInput(_))
+            ));
+        }
+    }
+}
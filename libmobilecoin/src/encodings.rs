@@ -1,10 +1,18 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-use crate::{common::*, LibMcError};
+use crate::{bip39::is_valid_bip39_entropy_len, common::*, keys::McPublicAddress, LibMcError};
+use core::convert::TryFrom;
 use libc::ssize_t;
-use mc_api::printable::PrintableWrapper;
+use mc_account_keys::PublicAddress;
+use mc_api::{
+    display::Error as ApiDisplayError,
+    external,
+    printable::{PaymentRequest, PrintableWrapper, TransferPayload},
+};
+use mc_crypto_keys::{CompressedRistrettoPublic, ReprBytes, RistrettoPublic};
 use mc_util_ffi::*;
 use protobuf::Message;
+use zeroize::Zeroizing;
 
 /* ==== PrintableWrapper ==== */
 
@@ -72,3 +80,804 @@ pub extern "C" fn mc_printable_wrapper_b58_decode(
             .expect("wrapper_bytes.len could not be converted to ssize_t"))
     })
 }
+
+/* ==== PublicAddress ==== */
+
+/// # Preconditions
+///
+/// * `public_address` - must be a valid `PublicAddress`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_public_address_to_b58(
+    public_address: FfiRefPtr<McPublicAddress>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedStr {
+    ffi_boundary_with_error(out_error, || {
+        let public_address =
+            PublicAddress::try_from_ffi(&public_address).expect("public_address is invalid");
+
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_public_address((&public_address).into());
+        let encoded = printable_wrapper.b58_encode()?;
+
+        Ok(FfiOwnedStr::ffi_try_from(encoded)
+            .expect("Resulting encoded string could not be converted to a C string"))
+    })
+}
+
+/// Decodes a b58 `PrintableWrapper` holding a public address. The fog out
+/// parameters are set to null if the address has no fog info.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid
+///   UTF-8.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+#[no_mangle]
+pub extern "C" fn mc_public_address_from_b58(
+    b58_encoded_string: FfiStr,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+    out_fog_report_url: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_report_id: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_authority_sig: FfiOptMutPtr<FfiOptOwnedPtr<McData>>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let b58_encoded_string =
+            <&str>::try_from_ffi(b58_encoded_string).expect("b58_encoded_string is invalid");
+        let public_address = public_address_from_b58(b58_encoded_string)?;
+
+        public_address.write(
+            out_view_public_key,
+            out_spend_public_key,
+            out_fog_report_url,
+            out_fog_report_id,
+            out_fog_authority_sig,
+        );
+        Ok(())
+    })
+}
+
+fn public_address_from_b58(b58_encoded_string: &str) -> Result<PublicAddressOutputs, LibMcError> {
+    let printable_wrapper = printable_wrapper_from_b58(b58_encoded_string)?;
+    if !printable_wrapper.has_public_address() {
+        return Err(LibMcError::UnexpectedWrapperType(
+            "expected a public address".to_owned(),
+        ));
+    }
+    PublicAddressOutputs::try_from(printable_wrapper.get_public_address())
+}
+
+/* ==== PaymentRequest ==== */
+
+#[repr(C)]
+pub struct McPaymentRequest<'a> {
+    pub public_address: FfiRefPtr<'a, McPublicAddress<'a>>,
+    pub value: u64,
+    pub token_id: u64,
+    pub memo: FfiStr<'a>,
+    pub payment_id: u64,
+}
+
+impl<'a> TryFromFfi<&McPaymentRequest<'a>> for PaymentRequest {
+    type Error = LibMcError;
+
+    fn try_from_ffi(src: &McPaymentRequest<'a>) -> Result<Self, LibMcError> {
+        let public_address = PublicAddress::try_from_ffi(&src.public_address)?;
+
+        let mut payment_request = PaymentRequest::new();
+        payment_request.set_public_address((&public_address).into());
+        payment_request.set_value(src.value);
+        payment_request.set_token_id(src.token_id);
+        payment_request.set_memo(String::try_from_ffi(src.memo)?);
+        payment_request.set_payment_id(src.payment_id);
+        Ok(payment_request)
+    }
+}
+
+/// # Preconditions
+///
+/// * `payment_request` - must be a valid `PaymentRequest`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_payment_request_to_b58(
+    payment_request: FfiRefPtr<McPaymentRequest>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedStr {
+    ffi_boundary_with_error(out_error, || {
+        let payment_request =
+            PaymentRequest::try_from_ffi(&payment_request).expect("payment_request is invalid");
+
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_payment_request(payment_request);
+        let encoded = printable_wrapper.b58_encode()?;
+
+        Ok(FfiOwnedStr::ffi_try_from(encoded)
+            .expect("Resulting encoded string could not be converted to a C string"))
+    })
+}
+
+/// A decoded payment request, as returned by `mc_payment_request_from_b58`.
+pub struct DecodedPaymentRequest {
+    /// Its fog report URL and id can be converted to C strings.
+    public_address: PublicAddress,
+    value: u64,
+    token_id: u64,
+    /// Can be converted to a C string.
+    memo: String,
+    payment_id: u64,
+}
+
+impl TryFrom<&PaymentRequest> for DecodedPaymentRequest {
+    type Error = LibMcError;
+
+    fn try_from(src: &PaymentRequest) -> Result<Self, LibMcError> {
+        let public_address = PublicAddressOutputs::try_from(src.get_public_address())?;
+        FfiOwnedStr::ffi_try_from(src.get_memo())?;
+        Ok(Self {
+            public_address: public_address.public_address,
+            value: src.get_value(),
+            token_id: src.get_token_id(),
+            memo: src.get_memo().to_owned(),
+            payment_id: src.get_payment_id(),
+        })
+    }
+}
+
+pub type McDecodedPaymentRequest = DecodedPaymentRequest;
+impl_into_ffi!(DecodedPaymentRequest);
+
+fn payment_request_from_b58(b58_encoded_string: &str) -> Result<DecodedPaymentRequest, LibMcError> {
+    let printable_wrapper = printable_wrapper_from_b58(b58_encoded_string)?;
+    if !printable_wrapper.has_payment_request() {
+        return Err(LibMcError::UnexpectedWrapperType(
+            "expected a payment request".to_owned(),
+        ));
+    }
+    DecodedPaymentRequest::try_from(printable_wrapper.get_payment_request())
+}
+
+/// Decodes a b58 `PrintableWrapper` holding a payment request, the inverse of
+/// `mc_payment_request_to_b58`.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid
+///   UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+#[no_mangle]
+pub extern "C" fn mc_payment_request_from_b58(
+    b58_encoded_string: FfiStr,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McDecodedPaymentRequest> {
+    ffi_boundary_with_error(out_error, || {
+        let b58_encoded_string =
+            <&str>::try_from_ffi(b58_encoded_string).expect("b58_encoded_string is invalid");
+        payment_request_from_b58(b58_encoded_string)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_free(
+    payment_request: FfiOptOwnedPtr<McDecodedPaymentRequest>,
+) {
+    ffi_boundary(|| {
+        let _ = payment_request;
+    })
+}
+
+/// Gets the requested public address. The fog out parameters are set to null
+/// if the address has no fog info.
+///
+/// # Preconditions
+///
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_get_public_address(
+    payment_request: FfiRefPtr<McDecodedPaymentRequest>,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+    out_fog_report_url: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_report_id: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_fog_authority_sig: FfiOptMutPtr<FfiOptOwnedPtr<McData>>,
+) -> bool {
+    ffi_boundary(|| {
+        PublicAddressOutputs::new(payment_request.public_address.clone())
+            .expect("public_address could not be converted")
+            .write(
+                out_view_public_key,
+                out_spend_public_key,
+                out_fog_report_url,
+                out_fog_report_id,
+                out_fog_authority_sig,
+            );
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_get_value(
+    payment_request: FfiRefPtr<McDecodedPaymentRequest>,
+) -> u64 {
+    ffi_boundary(|| payment_request.value)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_get_token_id(
+    payment_request: FfiRefPtr<McDecodedPaymentRequest>,
+) -> u64 {
+    ffi_boundary(|| payment_request.token_id)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_get_memo(
+    payment_request: FfiRefPtr<McDecodedPaymentRequest>,
+) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        FfiOwnedStr::ffi_try_from(payment_request.memo.as_str())
+            .expect("memo could not be converted to a C string")
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_payment_request_get_payment_id(
+    payment_request: FfiRefPtr<McDecodedPaymentRequest>,
+) -> u64 {
+    ffi_boundary(|| payment_request.payment_id)
+}
+
+/* ==== TransferPayload ==== */
+
+#[repr(C)]
+pub struct McTransferPayload<'a> {
+    /// Legacy 32-byte `RootIdentity` entropy. Exactly one of `root_entropy`
+    /// and `bip39_entropy` must be set.
+    pub root_entropy: FfiOptRefPtr<'a, McBuffer<'a>>,
+    /// BIP39 entropy: a multiple of 4 bytes, between 16 and 32 inclusive.
+    pub bip39_entropy: FfiOptRefPtr<'a, McBuffer<'a>>,
+    /// 32-byte `CompressedRistrettoPublic`
+    pub tx_out_public_key: FfiRefPtr<'a, McBuffer<'a>>,
+    pub memo: FfiStr<'a>,
+}
+
+/// Length in bytes of legacy `RootIdentity` entropy.
+const ROOT_ENTROPY_LEN: usize = 32;
+
+/// The entropy of the account a `TransferPayload` transfers.
+#[derive(Clone, Copy)]
+pub(crate) enum TransferPayloadEntropy<'a> {
+    /// Legacy `RootIdentity` entropy.
+    Root(&'a [u8]),
+    Bip39(&'a [u8]),
+}
+
+impl<'a> TransferPayloadEntropy<'a> {
+    /// Checks that exactly one of `root_entropy` and `bip39_entropy` is
+    /// non-empty, and that it has a valid length: 32 bytes for root entropy,
+    /// and any BIP39 entropy length for BIP39 entropy.
+    fn new(root_entropy: &'a [u8], bip39_entropy: &'a [u8]) -> Result<Self, LibMcError> {
+        let entropy = match (root_entropy.is_empty(), bip39_entropy.is_empty()) {
+            (false, true) => Self::Root(root_entropy),
+            (true, false) => Self::Bip39(bip39_entropy),
+            _ => {
+                return Err(LibMcError::InvalidInput(
+                    "Exactly one of root_entropy and bip39_entropy must be set".to_owned(),
+                ))
+            }
+        };
+        let valid = match entropy {
+            Self::Root(root_entropy) => root_entropy.len() == ROOT_ENTROPY_LEN,
+            Self::Bip39(bip39_entropy) => is_valid_bip39_entropy_len(bip39_entropy.len()),
+        };
+        if !valid {
+            return Err(LibMcError::InvalidInput(format!(
+                "Invalid transfer payload entropy length: {}",
+                entropy.bytes().len()
+            )));
+        }
+        Ok(entropy)
+    }
+
+    pub(crate) fn bytes(&self) -> &'a [u8] {
+        match self {
+            Self::Root(entropy) | Self::Bip39(entropy) => entropy,
+        }
+    }
+}
+
+/// The validated contents of a `TransferPayload`.
+pub(crate) struct TransferPayloadContents<'a> {
+    pub(crate) entropy: TransferPayloadEntropy<'a>,
+    pub(crate) tx_out_public_key: CompressedRistrettoPublic,
+    pub(crate) memo: &'a str,
+}
+
+impl<'a> TryFrom<&'a TransferPayload> for TransferPayloadContents<'a> {
+    type Error = LibMcError;
+
+    fn try_from(src: &'a TransferPayload) -> Result<Self, LibMcError> {
+        let entropy = TransferPayloadEntropy::new(src.get_root_entropy(), src.get_bip39_entropy())?;
+        let tx_out_public_key = CompressedRistrettoPublic::try_from(src.get_tx_out_public_key())
+            .map_err(|err| LibMcError::MalformedKey(format!("{:?}", err)))?;
+        Ok(Self {
+            entropy,
+            tx_out_public_key,
+            memo: src.get_memo(),
+        })
+    }
+}
+
+impl<'a> TryFromFfi<&McTransferPayload<'a>> for TransferPayload {
+    type Error = LibMcError;
+
+    fn try_from_ffi(src: &McTransferPayload<'a>) -> Result<Self, LibMcError> {
+        let tx_out_public_key = RistrettoPublic::try_from(src.tx_out_public_key.as_slice())
+            .map_err(|err| LibMcError::MalformedKey(format!("{:?}", err)))?;
+        let entropy = TransferPayloadEntropy::new(
+            src.root_entropy
+                .as_option()
+                .map_or(&[][..], |root_entropy| root_entropy.as_slice()),
+            src.bip39_entropy
+                .as_option()
+                .map_or(&[][..], |bip39_entropy| bip39_entropy.as_slice()),
+        )?;
+
+        let mut transfer_payload = TransferPayload::new();
+        match entropy {
+            TransferPayloadEntropy::Root(root_entropy) => {
+                transfer_payload.set_root_entropy(root_entropy.to_vec())
+            }
+            TransferPayloadEntropy::Bip39(bip39_entropy) => {
+                transfer_payload.set_bip39_entropy(bip39_entropy.to_vec())
+            }
+        }
+        transfer_payload
+            .set_tx_out_public_key((&CompressedRistrettoPublic::from(&tx_out_public_key)).into());
+        transfer_payload.set_memo(String::try_from_ffi(src.memo)?);
+        Ok(transfer_payload)
+    }
+}
+
+/// # Preconditions
+///
+/// * `transfer_payload` - must be a valid `TransferPayload`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::MalformedKey`
+#[no_mangle]
+pub extern "C" fn mc_transfer_payload_to_b58(
+    transfer_payload: FfiRefPtr<McTransferPayload>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedStr {
+    ffi_boundary_with_error(out_error, || {
+        let transfer_payload = TransferPayload::try_from_ffi(&transfer_payload)?;
+
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_transfer_payload(transfer_payload);
+        let encoded = printable_wrapper.b58_encode()?;
+
+        Ok(FfiOwnedStr::ffi_try_from(encoded)
+            .expect("Resulting encoded string could not be converted to a C string"))
+    })
+}
+
+/// A decoded transfer payload, as returned by `mc_transfer_payload_from_b58`.
+pub struct DecodedTransferPayload {
+    entropy: Zeroizing<Vec<u8>>,
+    is_root_entropy: bool,
+    tx_out_public_key: CompressedRistrettoPublic,
+    /// Can be converted to a C string.
+    memo: String,
+}
+
+impl TryFrom<&TransferPayload> for DecodedTransferPayload {
+    type Error = LibMcError;
+
+    fn try_from(src: &TransferPayload) -> Result<Self, LibMcError> {
+        let transfer_payload = TransferPayloadContents::try_from(src)?;
+        FfiOwnedStr::ffi_try_from(transfer_payload.memo)?;
+        Ok(Self {
+            entropy: Zeroizing::new(transfer_payload.entropy.bytes().to_vec()),
+            is_root_entropy: matches!(transfer_payload.entropy, TransferPayloadEntropy::Root(_)),
+            tx_out_public_key: transfer_payload.tx_out_public_key,
+            memo: transfer_payload.memo.to_owned(),
+        })
+    }
+}
+
+pub type McDecodedTransferPayload = DecodedTransferPayload;
+impl_into_ffi!(DecodedTransferPayload);
+
+fn transfer_payload_from_b58(
+    b58_encoded_string: &str,
+) -> Result<DecodedTransferPayload, LibMcError> {
+    let printable_wrapper = printable_wrapper_from_b58(b58_encoded_string)?;
+    if !printable_wrapper.has_transfer_payload() {
+        return Err(LibMcError::UnexpectedWrapperType(
+            "expected a transfer payload".to_owned(),
+        ));
+    }
+    DecodedTransferPayload::try_from(printable_wrapper.get_transfer_payload())
+}
+
+/// Decodes a b58 `PrintableWrapper` holding a transfer payload, the inverse of
+/// `mc_transfer_payload_to_b58`.
+///
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid
+///   UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::InvalidChecksum`
+/// * `LibMcError::UnexpectedWrapperType`
+/// * `LibMcError::MalformedKey`
+#[no_mangle]
+pub extern "C" fn mc_transfer_payload_from_b58(
+    b58_encoded_string: FfiStr,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McDecodedTransferPayload> {
+    ffi_boundary_with_error(out_error, || {
+        let b58_encoded_string =
+            <&str>::try_from_ffi(b58_encoded_string).expect("b58_encoded_string is invalid");
+        transfer_payload_from_b58(b58_encoded_string)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_transfer_payload_free(
+    transfer_payload: FfiOptOwnedPtr<McDecodedTransferPayload>,
+) {
+    ffi_boundary(|| {
+        let _ = transfer_payload;
+    })
+}
+
+/// Returns the legacy 32-byte `RootIdentity` entropy, or null if the payload
+/// holds BIP39 entropy instead.
+#[no_mangle]
+pub extern "C" fn mc_decoded_transfer_payload_get_root_entropy(
+    transfer_payload: FfiRefPtr<McDecodedTransferPayload>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| {
+        optional_data(
+            Some(transfer_payload.entropy.as_slice()).filter(|_| transfer_payload.is_root_entropy),
+        )
+    })
+}
+
+/// Returns the BIP39 entropy, or null if the payload holds legacy root
+/// entropy instead.
+#[no_mangle]
+pub extern "C" fn mc_decoded_transfer_payload_get_bip39_entropy(
+    transfer_payload: FfiRefPtr<McDecodedTransferPayload>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| {
+        optional_data(
+            Some(transfer_payload.entropy.as_slice()).filter(|_| !transfer_payload.is_root_entropy),
+        )
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_tx_out_public_key` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_decoded_transfer_payload_get_tx_out_public_key(
+    transfer_payload: FfiRefPtr<McDecodedTransferPayload>,
+    out_tx_out_public_key: FfiMutPtr<McMutableBuffer>,
+) -> bool {
+    ffi_boundary(|| {
+        out_tx_out_public_key
+            .into_mut()
+            .as_slice_mut_of_len(CompressedRistrettoPublic::size())
+            .expect("out_tx_out_public_key length is insufficient")
+            .copy_from_slice(&transfer_payload.tx_out_public_key.to_bytes());
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_decoded_transfer_payload_get_memo(
+    transfer_payload: FfiRefPtr<McDecodedTransferPayload>,
+) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        FfiOwnedStr::ffi_try_from(transfer_payload.memo.as_str())
+            .expect("memo could not be converted to a C string")
+    })
+}
+
+/* ==== Helpers ==== */
+
+fn printable_wrapper_from_b58(b58_encoded_string: &str) -> Result<PrintableWrapper, LibMcError> {
+    PrintableWrapper::b58_decode(b58_encoded_string.to_owned()).map_err(|err| match err {
+        ApiDisplayError::ChecksumError => LibMcError::InvalidChecksum(err.to_string()),
+        err => LibMcError::from(err),
+    })
+}
+
+/// A decoded public address with every fallible conversion already done, so
+/// that writing it out cannot fail halfway and leave some out parameters set.
+struct PublicAddressOutputs {
+    public_address: PublicAddress,
+    fog_report_url: FfiOptOwnedStr,
+    fog_report_id: FfiOptOwnedStr,
+}
+
+impl TryFrom<&external::PublicAddress> for PublicAddressOutputs {
+    type Error = LibMcError;
+
+    fn try_from(src: &external::PublicAddress) -> Result<Self, LibMcError> {
+        let public_address = PublicAddress::try_from(src)
+            .map_err(|err| LibMcError::MalformedKey(format!("{:?}", err)))?;
+        Self::new(public_address)
+    }
+}
+
+impl PublicAddressOutputs {
+    fn new(public_address: PublicAddress) -> Result<Self, LibMcError> {
+        let fog_report_url = FfiOptOwnedStr::ffi_try_from(public_address.fog_report_url())?;
+        let fog_report_id = FfiOptOwnedStr::ffi_try_from(public_address.fog_report_id())?;
+        Ok(Self {
+            public_address,
+            fog_report_url,
+            fog_report_id,
+        })
+    }
+
+    fn write(
+        self,
+        out_view_public_key: FfiMutPtr<McMutableBuffer>,
+        out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+        out_fog_report_url: FfiOptMutPtr<FfiOptOwnedStr>,
+        out_fog_report_id: FfiOptMutPtr<FfiOptOwnedStr>,
+        out_fog_authority_sig: FfiOptMutPtr<FfiOptOwnedPtr<McData>>,
+    ) {
+        out_view_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_view_public_key length is insufficient")
+            .copy_from_slice(&self.public_address.view_public_key().to_bytes());
+        out_spend_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_spend_public_key length is insufficient")
+            .copy_from_slice(&self.public_address.spend_public_key().to_bytes());
+
+        if let Some(out_fog_report_url) = out_fog_report_url.into_mut() {
+            *out_fog_report_url = self.fog_report_url;
+        }
+        if let Some(out_fog_report_id) = out_fog_report_id.into_mut() {
+            *out_fog_report_id = self.fog_report_id;
+        }
+        if let Some(out_fog_authority_sig) = out_fog_authority_sig.into_mut() {
+            *out_fog_authority_sig = optional_data(self.public_address.fog_authority_sig());
+        }
+    }
+}
+
+fn optional_data(bytes: Option<&[u8]>) -> FfiOptOwnedPtr<McData> {
+    bytes.map_or_else(Default::default, |bytes| {
+        FfiOwnedPtr::new(bytes.to_vec()).into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_account_keys::AccountKey;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn public_address_b58(public_address: &external::PublicAddress) -> String {
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_public_address(public_address.clone());
+        printable_wrapper.b58_encode().unwrap()
+    }
+
+    #[test]
+    fn public_address_round_trips_through_b58() {
+        let public_address =
+            AccountKey::random_with_fog(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+
+        let decoded = public_address_from_b58(&public_address_b58(&(&public_address).into()))
+            .unwrap()
+            .public_address;
+        assert_eq!(decoded, public_address);
+    }
+
+    #[test]
+    fn payment_request_b58_is_not_a_public_address() {
+        let public_address =
+            AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+        let mut payment_request = PaymentRequest::new();
+        payment_request.set_public_address((&public_address).into());
+        payment_request.set_value(10);
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_payment_request(payment_request);
+
+        assert!(matches!(
+            public_address_from_b58(&printable_wrapper.b58_encode().unwrap()),
+            Err(LibMcError::UnexpectedWrapperType(_))
+        ));
+    }
+
+    #[test]
+    fn public_address_b58_with_corrupted_checksum_is_rejected() {
+        let public_address =
+            AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+        // The checksum is the first 4 decoded bytes.
+        let mut bytes = bs58::decode(public_address_b58(&(&public_address).into()))
+            .into_vec()
+            .unwrap();
+        bytes[0] ^= 1;
+
+        assert!(matches!(
+            public_address_from_b58(&bs58::encode(bytes).into_string()),
+            Err(LibMcError::InvalidChecksum(_))
+        ));
+    }
+
+    #[test]
+    fn public_address_b58_with_invalid_key_is_rejected() {
+        let public_address =
+            AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+        let mut public_address = external::PublicAddress::from(&public_address);
+        // Not the canonical encoding of any Ristretto point.
+        public_address
+            .mut_view_public_key()
+            .set_data(vec![0xff; 32]);
+
+        assert!(matches!(
+            public_address_from_b58(&public_address_b58(&public_address)),
+            Err(LibMcError::MalformedKey(_))
+        ));
+    }
+
+    fn transfer_payload(root_entropy: &[u8], bip39_entropy: &[u8]) -> TransferPayload {
+        let tx_out_public_key = RistrettoPublic::from_random(&mut ChaCha20Rng::seed_from_u64(0));
+        let mut transfer_payload = TransferPayload::new();
+        transfer_payload.set_root_entropy(root_entropy.to_vec());
+        transfer_payload.set_bip39_entropy(bip39_entropy.to_vec());
+        transfer_payload
+            .set_tx_out_public_key((&CompressedRistrettoPublic::from(&tx_out_public_key)).into());
+        transfer_payload.set_memo("memo".to_owned());
+        transfer_payload
+    }
+
+    #[test]
+    fn transfer_payload_with_one_entropy_is_valid() {
+        let transfer_payload = transfer_payload(&[], &[7; 32]);
+        let contents = TransferPayloadContents::try_from(&transfer_payload).unwrap();
+        assert!(matches!(contents.entropy, TransferPayloadEntropy::Bip39(_)));
+        assert_eq!(contents.entropy.bytes(), &[7; 32]);
+        assert_eq!(contents.memo, "memo");
+
+        let transfer_payload = transfer_payload(&[9; 32], &[]);
+        let contents = TransferPayloadContents::try_from(&transfer_payload).unwrap();
+        assert!(matches!(contents.entropy, TransferPayloadEntropy::Root(_)));
+        assert_eq!(contents.entropy.bytes(), &[9; 32]);
+    }
+
+    #[test]
+    fn transfer_payload_without_entropy_is_rejected() {
+        assert!(matches!(
+            TransferPayloadContents::try_from(&transfer_payload(&[], &[])),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn transfer_payload_with_both_entropies_is_rejected() {
+        assert!(matches!(
+            TransferPayloadContents::try_from(&transfer_payload(&[9; 32], &[7; 32])),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn transfer_payload_bip39_entropy_may_be_any_bip39_length() {
+        for len in [16, 20, 24, 28, 32] {
+            let entropy = vec![7; len];
+            let transfer_payload = transfer_payload(&[], &entropy);
+            let contents = TransferPayloadContents::try_from(&transfer_payload).unwrap();
+            assert_eq!(contents.entropy.bytes(), entropy.as_slice());
+        }
+        for len in [1, 12, 15, 17, 30, 33, 36, 64] {
+            assert!(matches!(
+                TransferPayloadContents::try_from(&transfer_payload(&[], &vec![7; len])),
+                Err(LibMcError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn transfer_payload_root_entropy_must_be_32_bytes() {
+        for len in [1, 16, 24, 31, 33, 64] {
+            assert!(matches!(
+                TransferPayloadContents::try_from(&transfer_payload(&vec![9; len], &[])),
+                Err(LibMcError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn transfer_payload_round_trips_through_b58() {
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_transfer_payload(transfer_payload(&[], &[7; 16]));
+
+        let decoded = transfer_payload_from_b58(&printable_wrapper.b58_encode().unwrap()).unwrap();
+        assert!(!decoded.is_root_entropy);
+        assert_eq!(decoded.entropy.as_slice(), &[7; 16]);
+        assert_eq!(
+            decoded.tx_out_public_key.to_bytes().to_vec(),
+            printable_wrapper
+                .get_transfer_payload()
+                .get_tx_out_public_key()
+                .get_data()
+        );
+        assert_eq!(decoded.memo, "memo");
+    }
+
+    #[test]
+    fn payment_request_round_trips_through_b58() {
+        let public_address =
+            AccountKey::random_with_fog(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+        let mut payment_request = PaymentRequest::new();
+        payment_request.set_public_address((&public_address).into());
+        payment_request.set_value(10);
+        payment_request.set_token_id(2);
+        payment_request.set_memo("memo".to_owned());
+        payment_request.set_payment_id(42);
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_payment_request(payment_request);
+
+        let decoded = payment_request_from_b58(&printable_wrapper.b58_encode().unwrap()).unwrap();
+        assert_eq!(decoded.public_address, public_address);
+        assert_eq!(decoded.value, 10);
+        assert_eq!(decoded.token_id, 2);
+        assert_eq!(decoded.memo, "memo");
+        assert_eq!(decoded.payment_id, 42);
+
+        assert!(matches!(
+            transfer_payload_from_b58(&printable_wrapper.b58_encode().unwrap()),
+            Err(LibMcError::UnexpectedWrapperType(_))
+        ));
+    }
+
+    #[test]
+    fn transfer_payload_with_malformed_key_is_rejected() {
+        let mut transfer_payload = transfer_payload(&[], &[7; 32]);
+        transfer_payload
+            .mut_tx_out_public_key()
+            .set_data(vec![1; 31]);
+        assert!(matches!(
+            TransferPayloadContents::try_from(&transfer_payload),
+            Err(LibMcError::MalformedKey(_))
+        ));
+    }
+}
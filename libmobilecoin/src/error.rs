@@ -35,6 +35,18 @@ pub enum LibMcError {
     /// Invalid output: {0}
     InvalidOutput(String),
 
+    /// Unexpected printable wrapper type: {0}
+    UnexpectedWrapperType(String),
+
+    /// Invalid checksum: {0}
+    InvalidChecksum(String),
+
+    /// Malformed key: {0}
+    MalformedKey(String),
+
+    /// Ambiguous input: {0}
+    AmbiguousInput(String),
+
     /// Attestation verification failed: {0}
     AttestationVerificationFailed(String),
 
@@ -47,6 +59,9 @@ pub enum LibMcError {
     /// Unsupported CryptoBox version: {0}
     UnsupportedCryptoBoxVersion(String),
 
+    /// Unsupported account backup version: {0}
+    UnsupportedBackupVersion(String),
+
     /// Transaction cryptography error: {0}
     TransactionCrypto(String),
 
@@ -72,12 +87,17 @@ mod error_codes {
 
     pub const LIB_MC_ERROR_CODE_INVALID_INPUT: c_int = 100;
     pub const LIB_MC_ERROR_CODE_INVALID_OUTPUT: c_int = 101;
+    pub const LIB_MC_ERROR_CODE_UNEXPECTED_WRAPPER_TYPE: c_int = 102;
+    pub const LIB_MC_ERROR_CODE_INVALID_CHECKSUM: c_int = 103;
+    pub const LIB_MC_ERROR_CODE_MALFORMED_KEY: c_int = 104;
+    pub const LIB_MC_ERROR_CODE_AMBIGUOUS_INPUT: c_int = 105;
 
     pub const LIB_MC_ERROR_CODE_ATTESTATION_VERIFICATION_FAILED: c_int = 200;
 
     pub const LIB_MC_ERROR_CODE_AEAD: c_int = 300;
     pub const LIB_MC_ERROR_CODE_CIPHER: c_int = 301;
     pub const LIB_MC_ERROR_CODE_UNSUPPORTED_CRYPTO_BOX_VERSION: c_int = 302;
+    pub const LIB_MC_ERROR_CODE_UNSUPPORTED_BACKUP_VERSION: c_int = 303;
 
     pub const LIB_MC_ERROR_CODE_TRANSACTION_CRYPTO: c_int = 400;
 
@@ -92,6 +112,10 @@ impl LibMcError {
             LibMcError::Panic(_) => LIB_MC_ERROR_CODE_PANIC,
             LibMcError::InvalidInput(_) => LIB_MC_ERROR_CODE_INVALID_INPUT,
             LibMcError::InvalidOutput(_) => LIB_MC_ERROR_CODE_INVALID_OUTPUT,
+            LibMcError::UnexpectedWrapperType(_) => LIB_MC_ERROR_CODE_UNEXPECTED_WRAPPER_TYPE,
+            LibMcError::InvalidChecksum(_) => LIB_MC_ERROR_CODE_INVALID_CHECKSUM,
+            LibMcError::MalformedKey(_) => LIB_MC_ERROR_CODE_MALFORMED_KEY,
+            LibMcError::AmbiguousInput(_) => LIB_MC_ERROR_CODE_AMBIGUOUS_INPUT,
             LibMcError::AttestationVerificationFailed(_) => {
                 LIB_MC_ERROR_CODE_ATTESTATION_VERIFICATION_FAILED
             }
@@ -100,6 +124,7 @@ impl LibMcError {
             LibMcError::UnsupportedCryptoBoxVersion(_) => {
                 LIB_MC_ERROR_CODE_UNSUPPORTED_CRYPTO_BOX_VERSION
             }
+            LibMcError::UnsupportedBackupVersion(_) => LIB_MC_ERROR_CODE_UNSUPPORTED_BACKUP_VERSION,
             LibMcError::TransactionCrypto(_) => LIB_MC_ERROR_CODE_TRANSACTION_CRYPTO,
             LibMcError::FogPubkey(_) => LIB_MC_ERROR_CODE_FOG_PUBKEY,
             LibMcError::Poison => LIB_MC_ERROR_CODE_POISON,
@@ -0,0 +1,427 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! A stand-in for fog ingest and the fog report server, for offline tests of
+//! fog-enabled transaction building. Only built with the `fake-fog-ingest`
+//! feature.
+//!
+//! It holds the ingest keypair that fog hints are encrypted to, and a fog
+//! authority keypair that signs the report responses it produces. Real report
+//! responses carry an ingest attestation report, signed by a key that no test
+//! can produce outside of an SGX simulation build. A `McFogResolver` told to
+//! trust a fake ingest with `mc_fog_resolver_trust_fake_fog_ingest` accepts
+//! the responses signed by its authority key in place of attestation.
+
+use crate::{common::*, fog::McFogResolver, LibMcError};
+use core::convert::TryFrom;
+use mc_account_keys::PublicAddress;
+use mc_attest_core::VerificationReport;
+use mc_crypto_keys::{
+    DistinguishedEncoding, Ed25519Pair, Ed25519Public, Ed25519Signature, ReprBytes,
+    RistrettoPrivate, RistrettoPublic, RistrettoSignature,
+};
+use mc_fog_report_types::{Report, ReportResponse};
+use mc_fog_report_validation::{
+    FogPubkeyError, FogPubkeyResolver, FogReportResponses, FullyValidatedFogPubkey,
+};
+use mc_fog_sig_authority::Verifier as AuthorityVerifier;
+use mc_fog_sig_report::{Signer as ReportSigner, Verifier as ReportVerifier};
+use mc_transaction_core::{fog_hint::FogHint, tx::TxOut};
+use mc_util_ffi::*;
+use mc_util_uri::FogUri;
+use rand_core::{CryptoRng, RngCore};
+use std::str::FromStr;
+
+/* ==== McFakeFogIngest ==== */
+
+pub struct FakeFogIngest {
+    ingest_private_key: RistrettoPrivate,
+    authority_keypair: Ed25519Pair,
+}
+
+impl FakeFogIngest {
+    pub(crate) fn new(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        Self {
+            ingest_private_key: RistrettoPrivate::from_random(rng),
+            authority_keypair: Ed25519Pair::from_random(rng),
+        }
+    }
+
+    /// The DER-encoded subjectPublicKeyInfo of the fog authority key, which
+    /// fog-enabled public addresses sign with their view private key.
+    pub(crate) fn authority_spki(&self) -> Vec<u8> {
+        self.authority_keypair.public_key().to_der()
+    }
+
+    /// A report response advertising the ingest public key under `report_id`.
+    ///
+    /// The certificate chain of a real response is replaced by the authority
+    /// subjectPublicKeyInfo alone, and the ingest attestation report is left
+    /// empty, since only a resolver trusting this fake ingest can accept it.
+    pub(crate) fn report_response(
+        &self,
+        report_id: &str,
+        pubkey_expiry: u64,
+    ) -> Result<ReportResponse, LibMcError> {
+        let reports = vec![Report {
+            fog_report_id: report_id.to_owned(),
+            report: VerificationReport::default(),
+            pubkey_expiry,
+        }];
+        let signature = self
+            .authority_keypair
+            .sign_reports(&reports)
+            .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        Ok(ReportResponse {
+            reports,
+            chain: vec![self.authority_spki()],
+            signature: signature.as_ref().to_vec(),
+        })
+    }
+
+    /// Decrypts the fog hint of `tx_out`, returning the view public key of the
+    /// subaddress it was sent to.
+    fn decrypt_fog_hint(
+        &self,
+        tx_out: &TxOut,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<RistrettoPublic, LibMcError> {
+        // Decryption runs in constant time and overwrites `fog_hint` only on
+        // success, so it starts out as a random placeholder, as in ingest.
+        let mut fog_hint = FogHint::new(RistrettoPublic::from_random(rng));
+        let decrypted: bool =
+            FogHint::ct_decrypt(&self.ingest_private_key, &tx_out.e_fog_hint, &mut fog_hint).into();
+        if !decrypted {
+            return Err(LibMcError::InvalidInput(
+                "Fog hint was not encrypted for this ingest key".to_owned(),
+            ));
+        }
+        Ok(*fog_hint.get_view_pubkey())
+    }
+}
+
+pub type McFakeFogIngest = FakeFogIngest;
+impl_into_ffi!(FakeFogIngest);
+
+/// Generates a new ingest keypair and fog authority keypair.
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_create(
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+) -> FfiOptOwnedPtr<McFakeFogIngest> {
+    ffi_boundary(|| {
+        let mut rng = SdkRng::from_ffi(rng_callback);
+        FakeFogIngest::new(&mut rng)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_free(fake_fog_ingest: FfiOptOwnedPtr<McFakeFogIngest>) {
+    ffi_boundary(|| {
+        let _ = fake_fog_ingest;
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_ingest_public_key` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_get_public_key(
+    fake_fog_ingest: FfiRefPtr<McFakeFogIngest>,
+    out_ingest_public_key: FfiMutPtr<McMutableBuffer>,
+) -> bool {
+    ffi_boundary(|| {
+        let ingest_public_key = RistrettoPublic::from(&fake_fog_ingest.ingest_private_key);
+        out_ingest_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_ingest_public_key length is insufficient")
+            .copy_from_slice(&ingest_public_key.to_bytes());
+    })
+}
+
+/// Returns the DER-encoded subjectPublicKeyInfo of the fog authority key, to
+/// create fog-enabled account keys served by this fake ingest.
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_get_authority_spki(
+    fake_fog_ingest: FfiRefPtr<McFakeFogIngest>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| fake_fog_ingest.authority_spki())
+}
+
+/// Returns a binary-serialized `report.ReportResponse` Protobuf advertising
+/// the ingest public key under `report_id`, signed by the fog authority key.
+///
+/// It is accepted only by a `McFogResolver` that trusts this fake ingest.
+///
+/// # Preconditions
+///
+/// * `report_id` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_create_report_response(
+    fake_fog_ingest: FfiRefPtr<McFakeFogIngest>,
+    report_id: FfiStr,
+    pubkey_expiry: u64,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        let report_id = <&str>::try_from_ffi(report_id).expect("report_id is invalid");
+        let report_response = fake_fog_ingest.report_response(report_id, pubkey_expiry)?;
+        Ok(mc_util_serial::encode(&report_response))
+    })
+}
+
+/// Decrypts the fog hint of a `TxOut` and writes out the view public key of
+/// the subaddress it was sent to.
+///
+/// # Preconditions
+///
+/// * `tx_out_proto_bytes` - must be a valid binary-serialized `external.TxOut`
+///   Protobuf.
+/// * `out_view_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the fog hint was not encrypted for this
+///   ingest keypair.
+#[no_mangle]
+pub extern "C" fn mc_fake_fog_ingest_decrypt_fog_hint(
+    fake_fog_ingest: FfiRefPtr<McFakeFogIngest>,
+    tx_out_proto_bytes: FfiRefPtr<McBuffer>,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let tx_out: TxOut = mc_util_serial::decode(tx_out_proto_bytes.as_slice())
+            .expect("tx_out_proto_bytes could not be converted to TxOut");
+        let out_view_public_key = out_view_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_view_public_key length is insufficient");
+        let mut rng = SdkRng::from_ffi(rng_callback);
+
+        let view_public_key = fake_fog_ingest.decrypt_fog_hint(&tx_out, &mut rng)?;
+
+        out_view_public_key.copy_from_slice(&view_public_key.to_bytes());
+        Ok(())
+    })
+}
+
+/* ==== Test Fog Resolver Configuration ==== */
+
+/// The public keys of a `FakeFogIngest` that a `McFogResolver` has been told
+/// to trust.
+#[derive(Clone)]
+pub(crate) struct TrustedFakeFogIngest {
+    ingest_public_key: RistrettoPublic,
+    authority_public_key: Ed25519Public,
+}
+
+impl From<&FakeFogIngest> for TrustedFakeFogIngest {
+    fn from(src: &FakeFogIngest) -> Self {
+        Self {
+            ingest_public_key: RistrettoPublic::from(&src.ingest_private_key),
+            authority_public_key: src.authority_keypair.public_key(),
+        }
+    }
+}
+
+/// Resolves fog pubkeys from report responses produced by a trusted
+/// `FakeFogIngest`, in place of the attestation checks of a `FogResolver`.
+#[derive(Clone)]
+pub(crate) struct FakeFogResolver {
+    trusted: TrustedFakeFogIngest,
+    report_responses: FogReportResponses,
+}
+
+impl FakeFogResolver {
+    pub(crate) fn new(trusted: TrustedFakeFogIngest, report_responses: FogReportResponses) -> Self {
+        Self {
+            trusted,
+            report_responses,
+        }
+    }
+
+    /// Whether `report_response` is signed by the trusted authority key.
+    fn is_signed(&self, report_response: &ReportResponse) -> bool {
+        Ed25519Signature::try_from(&report_response.signature[..]).map_or(false, |signature| {
+            self.trusted
+                .authority_public_key
+                .verify_reports(&report_response.reports, &signature)
+                .is_ok()
+        })
+    }
+
+    /// Whether `recipient` signed the trusted authority key, that is, whether
+    /// it is served by the trusted fake ingest.
+    fn is_issued(&self, recipient: &PublicAddress) -> bool {
+        let authority_spki = self.trusted.authority_public_key.to_der();
+        recipient
+            .fog_authority_sig()
+            .and_then(|authority_sig| RistrettoSignature::try_from(authority_sig).ok())
+            .map_or(false, |authority_sig| {
+                recipient
+                    .view_public_key()
+                    .verify_authority(&authority_spki, &authority_sig)
+                    .is_ok()
+            })
+    }
+}
+
+impl FogPubkeyResolver for FakeFogResolver {
+    /// Responses that are not signed by the trusted authority, or that do not
+    /// contain the recipient's report id, are treated as missing.
+    fn get_fog_pubkey(
+        &self,
+        recipient: &PublicAddress,
+    ) -> Result<FullyValidatedFogPubkey, FogPubkeyError> {
+        let report_url = recipient
+            .fog_report_url()
+            .ok_or(FogPubkeyError::NoFogReportUrl)?;
+        let report_url = FogUri::from_str(report_url)
+            .map_err(FogPubkeyError::Url)?
+            .to_string();
+        let report_id = recipient.fog_report_id().unwrap_or_default();
+
+        self.report_responses
+            .get(&report_url)
+            .filter(|report_response| self.is_signed(report_response))
+            .filter(|_| self.is_issued(recipient))
+            .and_then(|report_response| {
+                report_response
+                    .reports
+                    .iter()
+                    .find(|report| report.fog_report_id == report_id)
+            })
+            .map(|report| FullyValidatedFogPubkey {
+                pubkey: self.trusted.ingest_public_key,
+                pubkey_expiry: report.pubkey_expiry,
+            })
+            .ok_or(FogPubkeyError::NoMatchingReportResponse(report_url))
+    }
+}
+
+/// Makes `fog_resolver` accept the report responses produced by
+/// `fake_fog_ingest`, skipping attestation. Responses from any other fog
+/// authority are then no longer accepted by `fog_resolver`.
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_trust_fake_fog_ingest(
+    fog_resolver: FfiMutPtr<McFogResolver>,
+    fake_fog_ingest: FfiRefPtr<McFakeFogIngest>,
+) {
+    ffi_boundary(|| {
+        fog_resolver
+            .into_mut()
+            .trust_fake_fog_ingest(TrustedFakeFogIngest::from(&*fake_fog_ingest));
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_account_keys::AccountKey;
+    use mc_transaction_core::{Amount, BlockVersion, TokenId};
+    use mc_transaction_std::{EmptyMemoBuilder, TransactionBuilder};
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    const REPORT_URL: &str = "fog://fog.example.com";
+    const REPORT_ID: &str = "";
+
+    fn fog_account_key(fake_fog_ingest: &FakeFogIngest, rng: &mut ChaCha20Rng) -> AccountKey {
+        AccountKey::new_with_fog(
+            &RistrettoPrivate::from_random(rng),
+            &RistrettoPrivate::from_random(rng),
+            REPORT_URL,
+            REPORT_ID.to_owned(),
+            fake_fog_ingest.authority_spki(),
+        )
+    }
+
+    fn fake_fog_resolver(
+        trusted: &FakeFogIngest,
+        report_response: ReportResponse,
+    ) -> FakeFogResolver {
+        let report_url = FogUri::from_str(REPORT_URL).unwrap().to_string();
+        FakeFogResolver::new(
+            TrustedFakeFogIngest::from(trusted),
+            [(report_url, report_response)].into_iter().collect(),
+        )
+    }
+
+    #[test]
+    fn trusted_report_response_resolves_to_ingest_public_key() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let recipient = fog_account_key(&fake_fog_ingest, &mut rng).default_subaddress();
+        let report_response = fake_fog_ingest.report_response(REPORT_ID, 100).unwrap();
+        // The response is handed to the resolver the way apps add it.
+        let report_response = mc_util_serial::decode(&mc_util_serial::encode(&report_response))
+            .expect("report response did not round-trip");
+        let fog_resolver = fake_fog_resolver(&fake_fog_ingest, report_response);
+
+        let fog_pubkey = fog_resolver.get_fog_pubkey(&recipient).unwrap();
+        assert_eq!(
+            fog_pubkey.pubkey,
+            RistrettoPublic::from(&fake_fog_ingest.ingest_private_key)
+        );
+        assert_eq!(fog_pubkey.pubkey_expiry, 100);
+    }
+
+    #[test]
+    fn report_response_of_another_authority_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let other_fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let recipient = fog_account_key(&fake_fog_ingest, &mut rng).default_subaddress();
+        let report_response = other_fake_fog_ingest
+            .report_response(REPORT_ID, 100)
+            .unwrap();
+        let fog_resolver = fake_fog_resolver(&fake_fog_ingest, report_response);
+
+        assert!(fog_resolver.get_fog_pubkey(&recipient).is_err());
+    }
+
+    #[test]
+    fn recipient_of_another_authority_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let other_fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let recipient = fog_account_key(&other_fake_fog_ingest, &mut rng).default_subaddress();
+        let report_response = fake_fog_ingest.report_response(REPORT_ID, 100).unwrap();
+        let fog_resolver = fake_fog_resolver(&fake_fog_ingest, report_response);
+
+        assert!(fog_resolver.get_fog_pubkey(&recipient).is_err());
+    }
+
+    #[test]
+    fn fog_hint_decrypts_to_recipient_view_public_key() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let other_fake_fog_ingest = FakeFogIngest::new(&mut rng);
+        let recipient = fog_account_key(&fake_fog_ingest, &mut rng).default_subaddress();
+        let report_response = fake_fog_ingest.report_response(REPORT_ID, 100).unwrap();
+        let mut transaction_builder = TransactionBuilder::new_with_box(
+            BlockVersion::MAX,
+            Amount::new(10, TokenId::from(0)),
+            fake_fog_resolver(&fake_fog_ingest, report_response),
+            Box::new(EmptyMemoBuilder::default()),
+        )
+        .unwrap();
+
+        let tx_out = transaction_builder
+            .add_output(Amount::new(1, TokenId::from(0)), &recipient, &mut rng)
+            .unwrap()
+            .tx_out;
+        assert_eq!(
+            fake_fog_ingest.decrypt_fog_hint(&tx_out, &mut rng).unwrap(),
+            *recipient.view_public_key()
+        );
+        assert!(other_fake_fog_ingest
+            .decrypt_fog_hint(&tx_out, &mut rng)
+            .is_err());
+    }
+}
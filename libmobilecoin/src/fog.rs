@@ -1,35 +1,207 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-use crate::{attest::McVerifier, common::*, keys::McPublicAddress, LibMcError};
+#[cfg(feature = "fake-fog-ingest")]
+use crate::fake_fog_ingest::{FakeFogResolver, TrustedFakeFogIngest};
+use crate::{
+    attest::McVerifier,
+    common::*,
+    keys::{McAccountKey, McAccountKeyHandle, McPublicAddress},
+    transaction::McTransactionBuilderRing,
+    LibMcError,
+};
 use core::convert::TryFrom;
 use libc::ssize_t;
 use mc_account_keys::PublicAddress;
 use mc_attest_verifier::Verifier;
+use mc_crypto_box::{CryptoBox, VersionedCryptoBox};
 use mc_crypto_keys::{ReprBytes, RistrettoPrivate, RistrettoPublic};
 use mc_fog_kex_rng::{BufferedRng, KexRngPubkey, NewFromKex, StoredRng, VersionedKexRng};
+use mc_fog_report_types::ReportResponse;
 use mc_fog_report_validation::{
-    FogPubkeyResolver, FogReportResponses, FogResolver, FullyValidatedFogPubkey,
+    ingest_report::IngestReportVerifier, FogPubkeyError, FogPubkeyResolver, FogReportResponses,
+    FogResolver, FullyValidatedFogPubkey,
+};
+use mc_fog_sig::{Error as FogSigError, Verifier as FogSigVerifier};
+use mc_fog_types::{
+    ledger::{
+        CheckKeyImagesRequest, CheckKeyImagesResponse, GetOutputsRequest, GetOutputsResponse,
+        KeyImageQuery, KeyImageResult, KeyImageResultCode, OutputResultCode,
+    },
+    view::{QueryRequest, QueryRequestAAD, QueryResponse, TxOutRecord, TxOutSearchResultCode},
+};
+use mc_transaction_core::{
+    membership_proofs::{compute_implied_merkle_root, is_membership_proof_valid},
+    ring_signature::KeyImage,
+    tx::{TxOut, TxOutMembershipProof},
 };
 use mc_util_ffi::*;
 use mc_util_serial::Message;
 use mc_util_uri::FogUri;
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    os::raw::c_int,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zeroize::Zeroize;
 
 /* ==== McFogResolver ==== */
 
-pub type McFogResolver = (FogReportResponses, Verifier);
-impl_into_ffi!((FogReportResponses, Verifier));
+/// The fog report responses fetched by the app, along with the verifier used
+/// to validate them when resolving a recipient's fog pubkey.
+pub struct FogReportCache {
+    report_responses: BTreeMap<String, CachedReportResponse>,
+    verifier: Verifier,
+    #[cfg(feature = "fake-fog-ingest")]
+    trusted_fake_fog_ingest: Option<TrustedFakeFogIngest>,
+}
+
+#[derive(Clone)]
+struct CachedReportResponse {
+    report_response: ReportResponse,
+    /// Seconds since the Unix epoch at which the response was added.
+    fetched_at: u64,
+}
+
+impl FogReportCache {
+    pub(crate) fn new(verifier: Verifier) -> Self {
+        Self {
+            report_responses: BTreeMap::new(),
+            verifier,
+            #[cfg(feature = "fake-fog-ingest")]
+            trusted_fake_fog_ingest: None,
+        }
+    }
+
+    pub(crate) fn fog_resolver(&self) -> Result<SdkFogResolver, LibMcError> {
+        let report_responses: FogReportResponses = self
+            .report_responses
+            .iter()
+            .map(|(report_url, cached)| (report_url.clone(), cached.report_response.clone()))
+            .collect();
+        #[cfg(feature = "fake-fog-ingest")]
+        let fake_fog_resolver = self
+            .trusted_fake_fog_ingest
+            .clone()
+            .map(|trusted| FakeFogResolver::new(trusted, report_responses.clone()));
+        let fog_resolver = FogResolver::new(report_responses, &self.verifier)
+            .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+        Ok(SdkFogResolver {
+            fog_resolver,
+            #[cfg(feature = "fake-fog-ingest")]
+            fake_fog_resolver,
+        })
+    }
+
+    pub(crate) fn add_report_response(
+        &mut self,
+        report_url: String,
+        report_response: ReportResponse,
+    ) {
+        let fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        self.report_responses.insert(
+            report_url,
+            CachedReportResponse {
+                report_response,
+                fetched_at,
+            },
+        );
+    }
+
+    #[cfg(feature = "fake-fog-ingest")]
+    pub(crate) fn trust_fake_fog_ingest(&mut self, trusted: TrustedFakeFogIngest) {
+        self.trusted_fake_fog_ingest = Some(trusted);
+    }
+
+    /// Report URLs whose cached response contains no reports, or any report
+    /// whose fog pubkey has expired as of `current_block_height`. A response is
+    /// stale as soon as one of its reports expires, even if the report a given
+    /// recipient uses is still valid.
+    fn stale_report_urls(&self, current_block_height: u64) -> Vec<String> {
+        self.report_responses
+            .iter()
+            .filter(|(_, cached)| {
+                let reports = &cached.report_response.reports;
+                reports.is_empty()
+                    || reports
+                        .iter()
+                        .any(|report| report.pubkey_expiry <= current_block_height)
+            })
+            .map(|(report_url, _)| report_url.clone())
+            .collect()
+    }
+}
+
+impl From<&FogReportCache> for StoredFogReportCache {
+    fn from(src: &FogReportCache) -> Self {
+        Self {
+            entries: src
+                .report_responses
+                .iter()
+                .map(|(report_url, cached)| StoredFogReportCacheEntry {
+                    report_url: report_url.clone(),
+                    report_response: mc_util_serial::encode(&cached.report_response),
+                    fetched_at: cached.fetched_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Serialized form of the report responses in a `FogReportCache`. The verifier
+/// is not stored, since it is part of the app's configuration rather than
+/// fetched state.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StoredFogReportCache {
+    #[prost(message, repeated, tag = "1")]
+    entries: Vec<StoredFogReportCacheEntry>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct StoredFogReportCacheEntry {
+    #[prost(string, tag = "1")]
+    report_url: String,
+    /// The binary-serialized `report.ReportResponse`. On the wire this is the
+    /// same as the embedded message.
+    #[prost(bytes, tag = "2")]
+    report_response: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    fetched_at: u64,
+}
+
+pub type McFogResolver = FogReportCache;
+impl_into_ffi!(FogReportCache);
+
+/// Resolves recipients' fog pubkeys from the report responses of a
+/// `FogReportCache`. In builds with the `fake-fog-ingest` feature, a cache that
+/// trusts a fake ingest resolves them without attestation instead.
+#[derive(Clone, Default)]
+pub struct SdkFogResolver {
+    fog_resolver: FogResolver,
+    #[cfg(feature = "fake-fog-ingest")]
+    fake_fog_resolver: Option<FakeFogResolver>,
+}
+
+impl FogPubkeyResolver for SdkFogResolver {
+    fn get_fog_pubkey(
+        &self,
+        recipient: &PublicAddress,
+    ) -> Result<FullyValidatedFogPubkey, FogPubkeyError> {
+        #[cfg(feature = "fake-fog-ingest")]
+        if let Some(fake_fog_resolver) = &self.fake_fog_resolver {
+            return fake_fog_resolver.get_fog_pubkey(recipient);
+        }
+        self.fog_resolver.get_fog_pubkey(recipient)
+    }
+}
 
 #[no_mangle]
 pub extern "C" fn mc_fog_resolver_create(
     fog_report_verifier: FfiRefPtr<McVerifier>,
 ) -> FfiOptOwnedPtr<McFogResolver> {
-    ffi_boundary(|| {
-        (
-            FogReportResponses::default(),
-            (*fog_report_verifier).clone(),
-        )
-    })
+    ffi_boundary(|| FogReportCache::new(Verifier::clone(&fog_report_verifier)))
 }
 
 #[no_mangle]
@@ -46,8 +218,7 @@ pub extern "C" fn mc_fog_resolver_get_fog_pubkey(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> FfiOptOwnedPtr<McFullyValidatedFogPubkey> {
     ffi_boundary_with_error(out_error, || {
-        let fog_resolver = FogResolver::new(fog_resolver.0.clone(), &fog_resolver.1)
-            .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+        let fog_resolver = fog_resolver.fog_resolver()?;
 
         let recipient = PublicAddress::try_from_ffi(&recipient)?;
         let fully_validated_fog_pubkey = fog_resolver.get_fog_pubkey(&recipient)?;
@@ -63,8 +234,7 @@ pub extern "C" fn mc_fog_resolver_get_fog_pubkey_from_protobuf_public_address(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> FfiOptOwnedPtr<McFullyValidatedFogPubkey> {
     ffi_boundary_with_error(out_error, || {
-        let fog_resolver = FogResolver::new(fog_resolver.0.clone(), &fog_resolver.1)
-            .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+        let fog_resolver = fog_resolver.fog_resolver()?;
 
         let recipient = mc_util_serial::decode(recipient_protobuf.as_slice())?;
         let fully_validated_fog_pubkey = fog_resolver.get_fog_pubkey(&recipient)?;
@@ -98,12 +268,259 @@ pub extern "C" fn mc_fog_resolver_add_report_response(
 
         fog_resolver
             .into_mut()
-            .0
-            .insert(report_url, report_response);
+            .add_report_response(report_url, report_response);
+        Ok(())
+    })
+}
+
+/// Returns the time at which the response for `report_url` was added to the
+/// resolver, in seconds since the Unix epoch, or 0 if there is none.
+///
+/// # Preconditions
+///
+/// * `report_url` - must be a nul-terminated C string containing a valid Fog
+///   report uri.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_get_report_fetched_at(
+    fog_resolver: FfiRefPtr<McFogResolver>,
+    report_url: FfiStr,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> u64 {
+    ffi_boundary_with_error(out_error, || {
+        let report_url =
+            <&str>::try_from_ffi(report_url).expect("report_url isn't a valid C string");
+        let report_url = FogUri::from_str(report_url)
+            .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+        Ok(fog_resolver
+            .report_responses
+            .get(&report_url.to_string())
+            .map_or(0, |cached| cached.fetched_at))
+    })
+}
+
+/// Returns the URLs of cached report responses that should be fetched again
+/// before sending at `current_block_height`. A URL is stale if its response
+/// has no reports or if ANY of its reports' fog pubkeys has expired, even when
+/// the report a particular recipient uses is still valid.
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_get_stale_report_urls(
+    fog_resolver: FfiRefPtr<McFogResolver>,
+    current_block_height: u64,
+) -> FfiOptOwnedPtr<McFogReportUrls> {
+    ffi_boundary(|| fog_resolver.stale_report_urls(current_block_height))
+}
+
+/// Serializes the cached report responses, with the time each was fetched.
+///
+/// # Preconditions
+///
+/// * `out_report_responses_proto_bytes` - must be null or else length must be
+///   >= `encoded.len`.
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_serialize_report_responses(
+    fog_resolver: FfiRefPtr<McFogResolver>,
+    out_report_responses_proto_bytes: FfiOptMutPtr<McMutableBuffer>,
+) -> ssize_t {
+    ffi_boundary(|| {
+        let stored_fog_report_cache = StoredFogReportCache::from(&*fog_resolver);
+        let encoded_len = stored_fog_report_cache.encoded_len();
+        if let Some(out_report_responses_proto_bytes) =
+            out_report_responses_proto_bytes.into_option()
+        {
+            let out_report_responses_proto_bytes = &mut out_report_responses_proto_bytes
+                .into_mut()
+                .as_slice_mut_of_len(encoded_len)
+                .expect("out_report_responses_proto_bytes length is insufficient");
+            stored_fog_report_cache
+                .encode(out_report_responses_proto_bytes)
+                .expect("prost::encode with correctly-sized buffer is no fail");
+        }
+        ssize_t::ffi_try_from(encoded_len).expect("encoded.len could not be converted to ssize_t")
+    })
+}
+
+/// Adds report responses serialized with
+/// `mc_fog_resolver_serialize_report_responses` to the resolver, keeping their
+/// original fetch times. A cached response for the same URL is replaced.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_deserialize_report_responses(
+    fog_resolver: FfiMutPtr<McFogResolver>,
+    report_responses_proto_bytes: FfiRefPtr<McBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let stored_fog_report_cache: StoredFogReportCache =
+            mc_util_serial::decode(report_responses_proto_bytes.as_slice())?;
+
+        let mut report_responses = BTreeMap::new();
+        for entry in stored_fog_report_cache.entries {
+            let report_url = FogUri::from_str(&entry.report_url)
+                .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+            report_responses.insert(
+                report_url.to_string(),
+                CachedReportResponse {
+                    report_response: mc_util_serial::decode(&entry.report_response)?,
+                    fetched_at: entry.fetched_at,
+                },
+            );
+        }
+
+        fog_resolver
+            .into_mut()
+            .report_responses
+            .extend(report_responses);
+        Ok(())
+    })
+}
+
+/// Returns the URLs in `report_urls` that have no cached response.
+#[no_mangle]
+pub extern "C" fn mc_fog_resolver_get_missing_report_urls(
+    fog_resolver: FfiRefPtr<McFogResolver>,
+    report_urls: FfiRefPtr<McFogReportUrls>,
+) -> FfiOptOwnedPtr<McFogReportUrls> {
+    ffi_boundary(|| missing_report_urls(&fog_resolver, &report_urls))
+}
+
+fn missing_report_urls(
+    fog_resolver: &FogReportCache,
+    report_urls: &McFogReportUrls,
+) -> McFogReportUrls {
+    report_urls
+        .iter()
+        .filter(|report_url| !fog_resolver.report_responses.contains_key(*report_url))
+        .cloned()
+        .collect()
+}
+
+/* ==== McFogReportUrls ==== */
+
+pub type McFogReportUrls = Vec<String>;
+impl_into_ffi!(Vec<String>);
+
+/// Adds `report_url` in its normalized `FogUri` form, unless it is empty or
+/// already present.
+fn add_report_url(report_urls: &mut McFogReportUrls, report_url: &str) -> Result<(), LibMcError> {
+    if report_url.is_empty() {
+        return Ok(());
+    }
+    let report_url = FogUri::from_str(report_url)
+        .map_err(|err| LibMcError::InvalidInput(err.to_string()))?
+        .to_string();
+    if !report_urls.contains(&report_url) {
+        report_urls.push(report_url);
+    }
+    Ok(())
+}
+
+/// Creates an empty list, to be filled with the report URLs that must be
+/// fetched before building a transaction.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_create() -> FfiOptOwnedPtr<McFogReportUrls> {
+    ffi_boundary(Vec::new)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_free(fog_report_urls: FfiOptOwnedPtr<McFogReportUrls>) {
+    ffi_boundary(|| {
+        let _ = fog_report_urls;
+    })
+}
+
+/// Adds the fog report URL of `recipient`, if it has fog.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report
+///   uri.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_add_public_address(
+    fog_report_urls: FfiMutPtr<McFogReportUrls>,
+    recipient: FfiRefPtr<McPublicAddress>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        if let Some(fog_info) = recipient.fog_info.as_option() {
+            let report_url = <&str>::try_from_ffi(fog_info.report_url)?;
+            add_report_url(fog_report_urls.into_mut(), report_url)?;
+        }
+        Ok(())
+    })
+}
+
+/// Adds the fog report URL of `account_key`, if it has fog. This covers the
+/// account's change subaddress and gift code subaddresses, which all share the
+/// account's fog info.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report
+///   uri.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_add_account_key(
+    fog_report_urls: FfiMutPtr<McFogReportUrls>,
+    account_key: FfiRefPtr<McAccountKey>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        if let Some(fog_info) = account_key.fog_info.as_option() {
+            let report_url = <&str>::try_from_ffi(fog_info.report_url)?;
+            add_report_url(fog_report_urls.into_mut(), report_url)?;
+        }
+        Ok(())
+    })
+}
+
+/// Like `mc_fog_report_urls_add_account_key`, but takes an account key handle.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - the report URL is not a valid Fog report
+///   uri.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_add_account_key_with_handle(
+    fog_report_urls: FfiMutPtr<McFogReportUrls>,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        if let Some(report_url) = account_key_handle.account_key().fog_report_url() {
+            add_report_url(fog_report_urls.into_mut(), report_url)?;
+        }
         Ok(())
     })
 }
 
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_get_count(
+    fog_report_urls: FfiRefPtr<McFogReportUrls>,
+) -> usize {
+    ffi_boundary(|| fog_report_urls.len())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the URL count.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_urls_get_url(
+    fog_report_urls: FfiRefPtr<McFogReportUrls>,
+    index: usize,
+) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        let report_url = fog_report_urls.get(index).expect("index out of bounds");
+        FfiOwnedStr::ffi_try_from(report_url.as_str())
+            .expect("report_url could not be converted to a C string")
+    })
+}
+
 /* ==== McFullyValidatedFogPubkey ==== */
 
 pub type McFullyValidatedFogPubkey = FullyValidatedFogPubkey;
@@ -283,3 +700,1985 @@ pub extern "C" fn mc_fog_rng_advance(
         fog_rng.into_mut().advance();
     })
 }
+
+/// Returns the next `count` outputs of `fog_rng`, leaving it unchanged.
+fn peek_outputs(fog_rng: &VersionedKexRng, count: usize) -> Vec<Vec<u8>> {
+    let mut fog_rng = fog_rng.clone();
+    (0..count)
+        .map(|_| {
+            let output = fog_rng.peek().to_vec();
+            fog_rng.advance();
+            output
+        })
+        .collect()
+}
+
+/// The total length of `count` fog rng outputs of length `output_len`, if it
+/// can be returned as an `ssize_t`.
+fn peek_outputs_len(count: usize, output_len: usize) -> Result<ssize_t, LibMcError> {
+    count
+        .checked_mul(output_len)
+        .and_then(|outputs_len| ssize_t::try_from(outputs_len).ok())
+        .ok_or_else(|| {
+            LibMcError::InvalidInput(format!(
+                "{} fog rng outputs of length {} are too large to return",
+                count, output_len
+            ))
+        })
+}
+
+/// Advances `fog_rng` by `count` outputs.
+fn advance_by(fog_rng: &mut VersionedKexRng, count: usize) {
+    for _ in 0..count {
+        fog_rng.advance();
+    }
+}
+
+/// Writes the next `count` outputs of `fog_rng` back to back into
+/// `out_outputs` without advancing it, and returns their total length, i.e.
+/// `count * output.len`.
+///
+/// # Preconditions
+///
+/// * `out_outputs` - must be null or else length must be >= `count *
+///   output.len`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `count * output.len` overflows.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_peek_many(
+    fog_rng: FfiRefPtr<McFogRng>,
+    count: usize,
+    out_outputs: FfiOptMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> ssize_t {
+    ffi_boundary_with_error(out_error, || {
+        let output_len = fog_rng.peek().len();
+        let outputs_len = peek_outputs_len(count, output_len)?;
+        if let Some(out_outputs) = out_outputs.into_option() {
+            let out_outputs = out_outputs
+                .into_mut()
+                .as_slice_mut_of_len(outputs_len as usize)
+                .expect("out_outputs length is insufficient");
+            for (out_output, output) in out_outputs
+                .chunks_exact_mut(output_len)
+                .zip(peek_outputs(&fog_rng, count))
+            {
+                out_output.copy_from_slice(&output);
+            }
+        }
+        Ok(outputs_len)
+    })
+}
+
+/// Advances `fog_rng` by `count` outputs.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_advance_by(fog_rng: FfiMutPtr<McFogRng>, count: usize) -> bool {
+    ffi_boundary(|| advance_by(fog_rng.into_mut(), count))
+}
+
+/* ==== McFogViewQuery ==== */
+
+/// A fog view query over a set of fog RNGs. The query owns copies of the RNGs
+/// it was given; processing a response advances those copies past every
+/// search key that was found, and `mc_fog_view_query_get_fog_rng` hands the
+/// updated state back.
+pub struct FogViewQuery {
+    start_from_user_event_id: i64,
+    start_from_block_index: u64,
+    fog_rngs: Vec<(VersionedKexRng, usize)>,
+}
+
+impl FogViewQuery {
+    fn request(&self) -> QueryRequest {
+        let mut request = QueryRequest::default();
+        for (fog_rng, search_key_count) in &self.fog_rngs {
+            request
+                .get_txos
+                .extend(peek_outputs(fog_rng, *search_key_count));
+        }
+        request
+    }
+
+    fn request_aad(&self) -> QueryRequestAAD {
+        QueryRequestAAD {
+            start_from_user_event_id: self.start_from_user_event_id,
+            start_from_block_index: self.start_from_block_index,
+        }
+    }
+
+    fn process_response(
+        &mut self,
+        response: &QueryResponse,
+        subaddress_view_private_key: &RistrettoPrivate,
+    ) -> Result<FogViewQueryResult, LibMcError> {
+        let found: HashMap<&[u8], &[u8]> = response
+            .tx_out_search_results
+            .iter()
+            .filter(|result| result.result_code == TxOutSearchResultCode::Found as u32)
+            .map(|result| (result.search_key.as_slice(), result.ciphertext.as_slice()))
+            .collect();
+
+        // Advance copies of the RNGs, so that a response that fails part-way
+        // through leaves the query as it was and can be retried.
+        let mut fog_rngs = self.fog_rngs.clone();
+        let mut tx_outs = Vec::new();
+        for (fog_rng, _) in &mut fog_rngs {
+            while let Some(ciphertext) = found.get(fog_rng.peek()) {
+                tx_outs.push(FogViewTxOut::decrypt(
+                    ciphertext,
+                    subaddress_view_private_key,
+                )?);
+                fog_rng.advance();
+            }
+        }
+        self.fog_rngs = fog_rngs;
+
+        Ok(FogViewQueryResult {
+            highest_processed_block_count: response.highest_processed_block_count,
+            next_start_from_user_event_id: response.next_start_from_user_event_id,
+            tx_outs,
+        })
+    }
+}
+
+pub type McFogViewQuery = FogViewQuery;
+impl_into_ffi!(FogViewQuery);
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_create(
+    start_from_user_event_id: i64,
+    start_from_block_index: u64,
+) -> FfiOptOwnedPtr<McFogViewQuery> {
+    ffi_boundary(|| FogViewQuery {
+        start_from_user_event_id,
+        start_from_block_index,
+        fog_rngs: Vec::new(),
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_free(fog_view_query: FfiOptOwnedPtr<McFogViewQuery>) {
+    ffi_boundary(|| {
+        let _ = fog_view_query;
+    })
+}
+
+/// Adds a copy of `fog_rng` to the query, which will ask for its next
+/// `search_key_count` outputs. Returns the index used to retrieve the advanced
+/// RNG with `mc_fog_view_query_get_fog_rng`.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_add_fog_rng(
+    fog_view_query: FfiMutPtr<McFogViewQuery>,
+    fog_rng: FfiRefPtr<McFogRng>,
+    search_key_count: usize,
+) -> usize {
+    ffi_boundary(|| {
+        let fog_rngs = &mut fog_view_query.into_mut().fog_rngs;
+        fog_rngs.push(((*fog_rng).clone(), search_key_count));
+        fog_rngs.len() - 1
+    })
+}
+
+/// # Preconditions
+///
+/// * `index` - must be an index returned by `mc_fog_view_query_add_fog_rng`.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_get_fog_rng(
+    fog_view_query: FfiRefPtr<McFogViewQuery>,
+    index: usize,
+) -> FfiOptOwnedPtr<McFogRng> {
+    ffi_boundary(|| {
+        fog_view_query
+            .fog_rngs
+            .get(index)
+            .expect("index out of bounds")
+            .0
+            .clone()
+    })
+}
+
+/// Returns the serialized `fog_view.QueryRequest` Protobuf, to be encrypted
+/// for the fog view enclave with `mc_attest_ake_encrypt`.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_create_request(
+    fog_view_query: FfiRefPtr<McFogViewQuery>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| mc_util_serial::encode(&fog_view_query.request()))
+}
+
+/// Returns the serialized `fog_view.QueryRequestAAD` Protobuf, to be passed as
+/// the associated data when encrypting the request.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_create_request_aad(
+    fog_view_query: FfiRefPtr<McFogViewQuery>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| mc_util_serial::encode(&fog_view_query.request_aad()))
+}
+
+/// Decrypts the TxOuts found for the query's search keys and advances each of
+/// the query's RNGs past its found outputs. Search results other than `Found`
+/// are ignored, so the corresponding RNGs stay in place and the keys are asked
+/// for again by the next query.
+///
+/// # Preconditions
+///
+/// * `query_response_proto_bytes` - must be the decrypted, binary-serialized
+///   `fog_view.QueryResponse` Protobuf answering this query's request.
+/// * `subaddress_view_private_key` - must be a valid 32-byte Ristretto-format
+///   scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_process_response(
+    fog_view_query: FfiMutPtr<McFogViewQuery>,
+    query_response_proto_bytes: FfiRefPtr<McBuffer>,
+    subaddress_view_private_key: FfiRefPtr<McBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McFogViewQueryResult> {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress_view_private_key =
+            RistrettoPrivate::try_from_ffi(&subaddress_view_private_key)
+                .expect("subaddress_view_private_key is not a valid RistrettoPrivate");
+        let response: QueryResponse =
+            mc_util_serial::decode(query_response_proto_bytes.as_slice())?;
+
+        fog_view_query
+            .into_mut()
+            .process_response(&response, &subaddress_view_private_key)
+    })
+}
+
+/* ==== McFogViewQueryResult ==== */
+
+pub struct FogViewTxOut {
+    tx_out: TxOut,
+    block_index: u64,
+    global_index: u64,
+    timestamp: u64,
+}
+
+impl FogViewTxOut {
+    fn decrypt(
+        ciphertext: &[u8],
+        subaddress_view_private_key: &RistrettoPrivate,
+    ) -> Result<Self, LibMcError> {
+        let (success, mut plaintext) =
+            VersionedCryptoBox::default().decrypt(subaddress_view_private_key, ciphertext)?;
+        if !bool::from(success) {
+            plaintext.zeroize();
+            return Err(LibMcError::Aead("MAC failed".to_owned()));
+        }
+        let tx_out_record: TxOutRecord = mc_util_serial::decode(&plaintext)?;
+        let fog_tx_out = tx_out_record
+            .get_fog_tx_out()
+            .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        let tx_out = fog_tx_out
+            .try_recover_tx_out(subaddress_view_private_key)
+            .map_err(|err| LibMcError::TransactionCrypto(format!("{:?}", err)))?;
+
+        Ok(Self {
+            tx_out,
+            block_index: tx_out_record.block_index,
+            global_index: tx_out_record.tx_out_global_index,
+            timestamp: tx_out_record.timestamp,
+        })
+    }
+}
+
+pub struct FogViewQueryResult {
+    highest_processed_block_count: u64,
+    next_start_from_user_event_id: i64,
+    tx_outs: Vec<FogViewTxOut>,
+}
+
+impl FogViewQueryResult {
+    fn tx_out(&self, index: usize) -> &FogViewTxOut {
+        self.tx_outs.get(index).expect("index out of bounds")
+    }
+}
+
+pub type McFogViewQueryResult = FogViewQueryResult;
+impl_into_ffi!(FogViewQueryResult);
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_free(
+    fog_view_query_result: FfiOptOwnedPtr<McFogViewQueryResult>,
+) {
+    ffi_boundary(|| {
+        let _ = fog_view_query_result;
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_highest_processed_block_count(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+) -> u64 {
+    ffi_boundary(|| fog_view_query_result.highest_processed_block_count)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_next_start_from_user_event_id(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+) -> i64 {
+    ffi_boundary(|| fog_view_query_result.next_start_from_user_event_id)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_tx_out_count(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+) -> usize {
+    ffi_boundary(|| fog_view_query_result.tx_outs.len())
+}
+
+/// Returns the serialized `external.TxOut` Protobuf at `index`.
+///
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_tx_out(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+    index: usize,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| mc_util_serial::encode(&fog_view_query_result.tx_out(index).tx_out))
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_tx_out_block_index(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+    index: usize,
+) -> u64 {
+    ffi_boundary(|| fog_view_query_result.tx_out(index).block_index)
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_tx_out_global_index(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+    index: usize,
+) -> u64 {
+    ffi_boundary(|| fog_view_query_result.tx_out(index).global_index)
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the TxOut count.
+#[no_mangle]
+pub extern "C" fn mc_fog_view_query_result_get_tx_out_timestamp(
+    fog_view_query_result: FfiRefPtr<McFogViewQueryResult>,
+    index: usize,
+) -> u64 {
+    ffi_boundary(|| fog_view_query_result.tx_out(index).timestamp)
+}
+
+/* ==== McFogRngSet ==== */
+
+#[derive(Clone)]
+struct FogRngSetEntry {
+    fog_rng: VersionedKexRng,
+    ingest_invocation_id: i64,
+    start_block: u64,
+    /// Set once the ingest invocation that published this RNG is
+    /// decommissioned.
+    last_ingested_block: Option<u64>,
+    /// Set once fog has processed every block of a decommissioned RNG and the
+    /// RNG's next output was not found, so it can never produce another TxOut.
+    exhausted: bool,
+}
+
+/// The fog RNGs of one subaddress, one per ingest key, keyed by the ingest
+/// key's public key.
+pub struct FogRngSet {
+    entries: BTreeMap<Vec<u8>, FogRngSetEntry>,
+}
+
+impl FogRngSet {
+    fn entry(&self, index: usize) -> &FogRngSetEntry {
+        self.entries
+            .values()
+            .nth(index)
+            .expect("index out of bounds")
+    }
+
+    fn query(
+        &self,
+        search_key_count: usize,
+        start_from_user_event_id: i64,
+        start_from_block_index: u64,
+    ) -> FogViewQuery {
+        FogViewQuery {
+            start_from_user_event_id,
+            start_from_block_index,
+            fog_rngs: self
+                .entries
+                .values()
+                .filter(|entry| !entry.exhausted)
+                .map(|entry| (entry.fog_rng.clone(), search_key_count))
+                .collect(),
+        }
+    }
+
+    fn process_response(
+        &mut self,
+        response: &QueryResponse,
+        subaddress_view_private_key: &RistrettoPrivate,
+    ) -> Result<FogViewQueryResult, LibMcError> {
+        // Apply the response to a copy of the set, so that a response that fails
+        // part-way through leaves the set as it was and can be retried.
+        let mut fog_rng_set = FogRngSet {
+            entries: self.entries.clone(),
+        };
+        let result = fog_rng_set.apply_response(response, subaddress_view_private_key)?;
+        *self = fog_rng_set;
+        Ok(result)
+    }
+
+    fn apply_response(
+        &mut self,
+        response: &QueryResponse,
+        subaddress_view_private_key: &RistrettoPrivate,
+    ) -> Result<FogViewQueryResult, LibMcError> {
+        for rng_record in &response.rng_records {
+            if !self.entries.contains_key(&rng_record.pubkey.public_key) {
+                let fog_rng = VersionedKexRng::try_from_kex_pubkey(
+                    &rng_record.pubkey,
+                    subaddress_view_private_key,
+                )?;
+                self.entries.insert(
+                    rng_record.pubkey.public_key.clone(),
+                    FogRngSetEntry {
+                        fog_rng,
+                        ingest_invocation_id: rng_record.ingest_invocation_id,
+                        start_block: rng_record.start_block,
+                        last_ingested_block: None,
+                        exhausted: false,
+                    },
+                );
+            }
+        }
+        for decommissioned in &response.decommissioned_ingest_invocations {
+            for entry in self.entries.values_mut() {
+                if entry.ingest_invocation_id == decommissioned.ingest_invocation_id {
+                    entry.last_ingested_block = Some(decommissioned.last_ingested_block);
+                }
+            }
+        }
+
+        // Replaying the response over a fresh query advances the same RNGs the
+        // caller's query would have, whatever search key count it asked for.
+        let mut query = self.query(0, 0, 0);
+        let result = query.process_response(response, subaddress_view_private_key)?;
+        let active_entries = self.entries.values_mut().filter(|entry| !entry.exhausted);
+        for (entry, (fog_rng, _)) in active_entries.zip(query.fog_rngs) {
+            entry.fog_rng = fog_rng;
+        }
+
+        let not_found: HashSet<&[u8]> = response
+            .tx_out_search_results
+            .iter()
+            .filter(|result| result.result_code == TxOutSearchResultCode::NotFound as u32)
+            .map(|result| result.search_key.as_slice())
+            .collect();
+        for entry in self.entries.values_mut() {
+            if let Some(last_ingested_block) = entry.last_ingested_block {
+                if response.highest_processed_block_count > last_ingested_block
+                    && not_found.contains(entry.fog_rng.peek())
+                {
+                    entry.exhausted = true;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl From<&FogRngSet> for StoredFogRngSet {
+    fn from(src: &FogRngSet) -> Self {
+        Self {
+            entries: src
+                .entries
+                .iter()
+                .map(|(public_key, entry)| StoredFogRngSetEntry {
+                    public_key: public_key.clone(),
+                    fog_rng: entry.fog_rng.clone().into(),
+                    ingest_invocation_id: entry.ingest_invocation_id,
+                    start_block: entry.start_block,
+                    last_ingested_block: entry.last_ingested_block,
+                    exhausted: entry.exhausted,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<StoredFogRngSet> for FogRngSet {
+    type Error = LibMcError;
+
+    fn try_from(src: StoredFogRngSet) -> Result<Self, LibMcError> {
+        let entries = src
+            .entries
+            .into_iter()
+            .map(|stored| {
+                let entry = FogRngSetEntry {
+                    fog_rng: VersionedKexRng::try_from(stored.fog_rng)?,
+                    ingest_invocation_id: stored.ingest_invocation_id,
+                    start_block: stored.start_block,
+                    last_ingested_block: stored.last_ingested_block,
+                    exhausted: stored.exhausted,
+                };
+                Ok((stored.public_key, entry))
+            })
+            .collect::<Result<_, LibMcError>>()?;
+        Ok(Self { entries })
+    }
+}
+
+/// Serialized form of a `FogRngSet`. Each RNG is kept as the same `StoredRng`
+/// used by `mc_fog_rng_serialize_proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct StoredFogRngSet {
+    #[prost(message, repeated, tag = "1")]
+    entries: Vec<StoredFogRngSetEntry>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct StoredFogRngSetEntry {
+    #[prost(bytes, tag = "1")]
+    public_key: Vec<u8>,
+    #[prost(message, required, tag = "2")]
+    fog_rng: StoredRng,
+    #[prost(int64, tag = "3")]
+    ingest_invocation_id: i64,
+    #[prost(uint64, tag = "4")]
+    start_block: u64,
+    #[prost(uint64, optional, tag = "5")]
+    last_ingested_block: Option<u64>,
+    #[prost(bool, tag = "6")]
+    exhausted: bool,
+}
+
+pub type McFogRngSet = FogRngSet;
+impl_into_ffi!(FogRngSet);
+
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_create() -> FfiOptOwnedPtr<McFogRngSet> {
+    ffi_boundary(|| FogRngSet {
+        entries: BTreeMap::new(),
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_free(fog_rng_set: FfiOptOwnedPtr<McFogRngSet>) {
+    ffi_boundary(|| {
+        let _ = fog_rng_set;
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_fog_rng_set_proto_bytes` - must be null or else length must be >=
+///   `encoded.len`.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_serialize_proto(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    out_fog_rng_set_proto_bytes: FfiOptMutPtr<McMutableBuffer>,
+) -> ssize_t {
+    ffi_boundary(|| {
+        let stored_fog_rng_set = StoredFogRngSet::from(&*fog_rng_set);
+        let encoded_len = stored_fog_rng_set.encoded_len();
+        if let Some(out_fog_rng_set_proto_bytes) = out_fog_rng_set_proto_bytes.into_option() {
+            let out_fog_rng_set_proto_bytes = &mut out_fog_rng_set_proto_bytes
+                .into_mut()
+                .as_slice_mut_of_len(encoded_len)
+                .expect("out_fog_rng_set_proto_bytes length is insufficient");
+            stored_fog_rng_set
+                .encode(out_fog_rng_set_proto_bytes)
+                .expect("prost::encode with correctly-sized buffer is no fail");
+        }
+        ssize_t::ffi_try_from(encoded_len).expect("encoded.len could not be converted to ssize_t")
+    })
+}
+
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_deserialize_proto(
+    fog_rng_set_proto_bytes: FfiRefPtr<McBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McFogRngSet> {
+    ffi_boundary_with_error(out_error, || {
+        let stored_fog_rng_set: StoredFogRngSet =
+            mc_util_serial::decode(fog_rng_set_proto_bytes.as_slice())
+                .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        FogRngSet::try_from(stored_fog_rng_set)
+    })
+}
+
+/// Creates a query asking for the next `search_key_count` outputs of every RNG
+/// in the set that is not exhausted.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_create_query(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    search_key_count: usize,
+    start_from_user_event_id: i64,
+    start_from_block_index: u64,
+) -> FfiOptOwnedPtr<McFogViewQuery> {
+    ffi_boundary(|| {
+        fog_rng_set.query(
+            search_key_count,
+            start_from_user_event_id,
+            start_from_block_index,
+        )
+    })
+}
+
+/// Processes the response to a query created with
+/// `mc_fog_rng_set_create_query`: adds an RNG for each new `RngRecord`, marks
+/// RNGs of decommissioned ingest invocations, decrypts the TxOuts found, and
+/// advances the set's RNGs past them.
+///
+/// # Preconditions
+///
+/// * `query_response_proto_bytes` - must be the decrypted, binary-serialized
+///   `fog_view.QueryResponse` Protobuf.
+/// * `subaddress_view_private_key` - must be a valid 32-byte Ristretto-format
+///   scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Aead`
+/// * `LibMcError::UnsupportedCryptoBoxVersion`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_process_query_response(
+    fog_rng_set: FfiMutPtr<McFogRngSet>,
+    query_response_proto_bytes: FfiRefPtr<McBuffer>,
+    subaddress_view_private_key: FfiRefPtr<McBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McFogViewQueryResult> {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress_view_private_key =
+            RistrettoPrivate::try_from_ffi(&subaddress_view_private_key)
+                .expect("subaddress_view_private_key is not a valid RistrettoPrivate");
+        let response: QueryResponse =
+            mc_util_serial::decode(query_response_proto_bytes.as_slice())?;
+
+        fog_rng_set
+            .into_mut()
+            .process_response(&response, &subaddress_view_private_key)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_get_count(fog_rng_set: FfiRefPtr<McFogRngSet>) -> usize {
+    ffi_boundary(|| fog_rng_set.entries.len())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_get_fog_rng(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    index: usize,
+) -> FfiOptOwnedPtr<McFogRng> {
+    ffi_boundary(|| fog_rng_set.entry(index).fog_rng.clone())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_get_ingest_invocation_id(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    index: usize,
+) -> i64 {
+    ffi_boundary(|| fog_rng_set.entry(index).ingest_invocation_id)
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_get_start_block(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    index: usize,
+) -> u64 {
+    ffi_boundary(|| fog_rng_set.entry(index).start_block)
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_is_decommissioned(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    index: usize,
+) -> bool {
+    ffi_boundary(|| fog_rng_set.entry(index).last_ingested_block.is_some())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the set's count.
+#[no_mangle]
+pub extern "C" fn mc_fog_rng_set_is_exhausted(
+    fog_rng_set: FfiRefPtr<McFogRngSet>,
+    index: usize,
+) -> bool {
+    ffi_boundary(|| fog_rng_set.entry(index).exhausted)
+}
+
+/* ==== McFogKeyImageQuery ==== */
+
+pub type McFogKeyImageQuery = Vec<KeyImageQuery>;
+impl_into_ffi!(Vec<KeyImageQuery>);
+
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_query_create() -> FfiOptOwnedPtr<McFogKeyImageQuery> {
+    ffi_boundary(Vec::new)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_query_free(
+    fog_key_image_query: FfiOptOwnedPtr<McFogKeyImageQuery>,
+) {
+    ffi_boundary(|| {
+        let _ = fog_key_image_query;
+    })
+}
+
+/// Adds `key_image` to the query. Fog ledger only reports spends at or after
+/// `start_block`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_query_add_key_image(
+    fog_key_image_query: FfiMutPtr<McFogKeyImageQuery>,
+    key_image: FfiRefPtr<McBuffer>,
+    start_block: u64,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let key_image = KeyImage::from(*<&[u8; 32]>::try_from_ffi(&key_image)?);
+        fog_key_image_query.into_mut().push(KeyImageQuery {
+            key_image,
+            start_block,
+        });
+        Ok(())
+    })
+}
+
+/// Returns the serialized `fog_ledger.CheckKeyImagesRequest` Protobuf, to be
+/// encrypted for the fog ledger enclave with `mc_attest_ake_encrypt`.
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_query_create_request(
+    fog_key_image_query: FfiRefPtr<McFogKeyImageQuery>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| {
+        mc_util_serial::encode(&CheckKeyImagesRequest {
+            queries: (*fog_key_image_query).clone(),
+        })
+    })
+}
+
+/* ==== McFogKeyImageResults ==== */
+
+pub type McFogKeyImageStatus = c_int;
+
+pub const MC_FOG_KEY_IMAGE_STATUS_NOT_SPENT: McFogKeyImageStatus = 0;
+pub const MC_FOG_KEY_IMAGE_STATUS_SPENT: McFogKeyImageStatus = 1;
+/// Fog ledger could not check the key image, e.g. because it was malformed.
+pub const MC_FOG_KEY_IMAGE_STATUS_ERROR: McFogKeyImageStatus = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct McFogKeyImageResult {
+    status: McFogKeyImageStatus,
+    /// Index of the block in which the key image was spent. Only meaningful
+    /// when `status` is `MC_FOG_KEY_IMAGE_STATUS_SPENT`.
+    spent_at: u64,
+    /// Timestamp of the block at `spent_at`, in seconds since the Unix epoch.
+    /// Zero when the status is not spent or fog has no timestamp for it.
+    timestamp: u64,
+}
+
+impl From<&KeyImageResult> for McFogKeyImageResult {
+    fn from(result: &KeyImageResult) -> Self {
+        let status = if result.key_image_result_code == KeyImageResultCode::Spent as u32 {
+            MC_FOG_KEY_IMAGE_STATUS_SPENT
+        } else if result.key_image_result_code == KeyImageResultCode::NotSpent as u32 {
+            MC_FOG_KEY_IMAGE_STATUS_NOT_SPENT
+        } else {
+            MC_FOG_KEY_IMAGE_STATUS_ERROR
+        };
+        let spent = status == MC_FOG_KEY_IMAGE_STATUS_SPENT;
+        McFogKeyImageResult {
+            status,
+            spent_at: if spent { result.spent_at } else { 0 },
+            timestamp: if spent { result.timestamp } else { 0 },
+        }
+    }
+}
+
+pub type McFogKeyImageResults = CheckKeyImagesResponse;
+impl_into_ffi!(CheckKeyImagesResponse);
+
+/// # Preconditions
+///
+/// * `check_key_images_response_proto_bytes` - must be the decrypted,
+///   binary-serialized `fog_ledger.CheckKeyImagesResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_results_create(
+    check_key_images_response_proto_bytes: FfiRefPtr<McBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McFogKeyImageResults> {
+    ffi_boundary_with_error(out_error, || {
+        Ok(mc_util_serial::decode(
+            check_key_images_response_proto_bytes.as_slice(),
+        )?)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_results_free(
+    fog_key_image_results: FfiOptOwnedPtr<McFogKeyImageResults>,
+) {
+    ffi_boundary(|| {
+        let _ = fog_key_image_results;
+    })
+}
+
+/// The number of blocks fog ledger had processed when it answered.
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_results_get_num_blocks(
+    fog_key_image_results: FfiRefPtr<McFogKeyImageResults>,
+) -> u64 {
+    ffi_boundary(|| fog_key_image_results.num_blocks)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_results_get_count(
+    fog_key_image_results: FfiRefPtr<McFogKeyImageResults>,
+) -> usize {
+    ffi_boundary(|| fog_key_image_results.results.len())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the result count.
+/// * `out_key_image` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_fog_key_image_results_get_result(
+    fog_key_image_results: FfiRefPtr<McFogKeyImageResults>,
+    index: usize,
+    out_key_image: FfiMutPtr<McMutableBuffer>,
+    out_result: FfiMutPtr<McFogKeyImageResult>,
+) -> bool {
+    ffi_boundary(|| {
+        let result = fog_key_image_results
+            .results
+            .get(index)
+            .expect("index out of bounds");
+        out_key_image
+            .into_mut()
+            .as_slice_mut_of_len(KeyImage::size())
+            .expect("out_key_image length is insufficient")
+            .copy_from_slice(result.key_image.as_ref());
+        *out_result.into_mut() = McFogKeyImageResult::from(result);
+    })
+}
+
+/* ==== McFogMerkleProofQuery ==== */
+
+/// Checks a fog ledger `GetOutputsResponse` against the request it answers and
+/// returns the outputs paired with their membership proofs, in request order.
+///
+/// Every requested output must exist, and every proof must be valid for its
+/// output and imply the same merkle root, so that the outputs can be used
+/// together in one ring.
+fn outputs_from_response(
+    request: &GetOutputsRequest,
+    response: GetOutputsResponse,
+) -> Result<Vec<(TxOut, TxOutMembershipProof)>, LibMcError> {
+    if response.results.len() != request.indices.len() {
+        return Err(LibMcError::InvalidInput(format!(
+            "Expected {} output results, got {}",
+            request.indices.len(),
+            response.results.len()
+        )));
+    }
+
+    let mut root_hash = None;
+    let mut outputs = Vec::with_capacity(response.results.len());
+    for (index, result) in request.indices.iter().zip(response.results) {
+        if result.index != *index {
+            return Err(LibMcError::InvalidInput(format!(
+                "Expected output result for index {}, got {}",
+                index, result.index
+            )));
+        }
+        if result.result_code != OutputResultCode::Exists as u32 {
+            return Err(LibMcError::InvalidInput(format!(
+                "Output {} could not be retrieved, result code: {}",
+                index, result.result_code
+            )));
+        }
+
+        let root_hash = match root_hash {
+            Some(root_hash) => root_hash,
+            None => {
+                let root = compute_implied_merkle_root(&result.proof)
+                    .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+                *root_hash.insert(root.hash.0)
+            }
+        };
+        let is_valid = is_membership_proof_valid(&result.output, &result.proof, &root_hash)
+            .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        if !is_valid {
+            return Err(LibMcError::InvalidInput(format!(
+                "Membership proof for output {} does not match the merkle root",
+                index
+            )));
+        }
+
+        outputs.push((result.output, result.proof));
+    }
+    Ok(outputs)
+}
+
+pub type McFogMerkleProofQuery = GetOutputsRequest;
+impl_into_ffi!(GetOutputsRequest);
+
+/// Creates a query for the membership proofs of a set of outputs. All proofs
+/// are taken as of `merkle_root_block`, so they share one merkle root.
+#[no_mangle]
+pub extern "C" fn mc_fog_merkle_proof_query_create(
+    merkle_root_block: u64,
+) -> FfiOptOwnedPtr<McFogMerkleProofQuery> {
+    ffi_boundary(|| GetOutputsRequest {
+        indices: Vec::new(),
+        merkle_root_block,
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_fog_merkle_proof_query_free(
+    fog_merkle_proof_query: FfiOptOwnedPtr<McFogMerkleProofQuery>,
+) {
+    ffi_boundary(|| {
+        let _ = fog_merkle_proof_query;
+    })
+}
+
+/// Adds the output with global index `tx_out_global_index` to the query.
+#[no_mangle]
+pub extern "C" fn mc_fog_merkle_proof_query_add_index(
+    fog_merkle_proof_query: FfiMutPtr<McFogMerkleProofQuery>,
+    tx_out_global_index: u64,
+) -> bool {
+    ffi_boundary(|| {
+        fog_merkle_proof_query
+            .into_mut()
+            .indices
+            .push(tx_out_global_index)
+    })
+}
+
+/// Returns the serialized `fog_ledger.GetOutputsRequest` Protobuf, to be
+/// encrypted for the fog ledger enclave with `mc_attest_ake_encrypt`.
+#[no_mangle]
+pub extern "C" fn mc_fog_merkle_proof_query_create_request(
+    fog_merkle_proof_query: FfiRefPtr<McFogMerkleProofQuery>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary(|| mc_util_serial::encode(&*fog_merkle_proof_query))
+}
+
+/// Fills `out_ring` with the outputs and membership proofs in the response,
+/// in the order their indices were added to the query. `out_ring` is left
+/// empty if the response fails validation.
+///
+/// # Preconditions
+///
+/// * `get_outputs_response_proto_bytes` - must be the decrypted,
+///   binary-serialized `fog_ledger.GetOutputsResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `out_ring` is not empty, or the response is
+///   malformed, does not answer the query, reports a missing output, or
+///   contains a proof that is invalid or implies a different merkle root than
+///   the others.
+#[no_mangle]
+pub extern "C" fn mc_fog_merkle_proof_query_process_response(
+    fog_merkle_proof_query: FfiRefPtr<McFogMerkleProofQuery>,
+    get_outputs_response_proto_bytes: FfiRefPtr<McBuffer>,
+    out_ring: FfiMutPtr<McTransactionBuilderRing>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let out_ring = out_ring.into_mut();
+        if !out_ring.is_empty() {
+            return Err(LibMcError::InvalidInput(
+                "out_ring must be empty, so its proofs all share one merkle root".to_owned(),
+            ));
+        }
+        let response: GetOutputsResponse =
+            mc_util_serial::decode(get_outputs_response_proto_bytes.as_slice())?;
+        let outputs = outputs_from_response(&fog_merkle_proof_query, response)?;
+        out_ring.extend(outputs);
+        Ok(())
+    })
+}
+
+/* ==== McFogReportValidation ==== */
+
+pub type McFogReportCheckStatus = c_int;
+
+/// The check depends on an earlier one that failed, e.g. there is no report to
+/// check the attestation of.
+pub const MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED: McFogReportCheckStatus = 0;
+pub const MC_FOG_REPORT_CHECK_STATUS_PASSED: McFogReportCheckStatus = 1;
+pub const MC_FOG_REPORT_CHECK_STATUS_FAILED: McFogReportCheckStatus = 2;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct McFogReportValidationResult {
+    /// Whether the response contains a report with the recipient's report id.
+    report_id_found: bool,
+    /// The block index at which the report's fog pubkey expires, or 0 if the
+    /// report was not found.
+    pubkey_expiry: u64,
+    /// Validity of the response's certificate chain, and verification of its
+    /// root against the recipient's fog authority signature.
+    certificate_chain_status: McFogReportCheckStatus,
+    /// Verification of the signature over the reports by the chain's leaf
+    /// certificate.
+    report_signature_status: McFogReportCheckStatus,
+    /// Verification of the ingest enclave's attestation report.
+    attestation_status: McFogReportCheckStatus,
+    /// Whether the fog pubkey expires after the current block height.
+    expiry_status: McFogReportCheckStatus,
+}
+
+/// Outcome of each check of `validate_report_response`, with a description of
+/// every check that failed.
+struct ReportResponseValidation {
+    result: McFogReportValidationResult,
+    failures: Vec<String>,
+}
+
+/// Runs the checks of `mc_fog_report_response_validate`. The certificate chain
+/// and report signature are checked by `mc_fog_sig`, and the attestation by
+/// `IngestReportVerifier`, which is what `FogResolver` runs as well.
+fn validate_report_response(
+    report_response: &ReportResponse,
+    fog_report_verifier: &Verifier,
+    recipient: &PublicAddress,
+    current_block_height: u64,
+) -> Result<ReportResponseValidation, LibMcError> {
+    let report_url = recipient
+        .fog_report_url()
+        .ok_or_else(|| LibMcError::InvalidInput("Recipient has no fog info".to_owned()))?;
+    FogUri::from_str(report_url).map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+    let report_id = recipient.fog_report_id().unwrap_or_default();
+
+    let mut failures = Vec::new();
+
+    let report = report_response
+        .reports
+        .iter()
+        .find(|report| report.fog_report_id == report_id);
+    if report.is_none() {
+        failures.push(format!("No report with id {:?} in the response", report_id));
+    }
+
+    // `verify_fog_sig` checks the chain before the signature it carries, so an
+    // error about the report signature means the chain itself passed.
+    let (certificate_chain_status, report_signature_status) =
+        match recipient.verify_fog_sig(report_response) {
+            Ok(()) => (
+                MC_FOG_REPORT_CHECK_STATUS_PASSED,
+                MC_FOG_REPORT_CHECK_STATUS_PASSED,
+            ),
+            Err(err @ FogSigError::Report(_)) => {
+                failures.push(format!("Report signature verification failed: {}", err));
+                (
+                    MC_FOG_REPORT_CHECK_STATUS_PASSED,
+                    MC_FOG_REPORT_CHECK_STATUS_FAILED,
+                )
+            }
+            Err(err) => {
+                failures.push(format!("Certificate chain verification failed: {}", err));
+                (
+                    MC_FOG_REPORT_CHECK_STATUS_FAILED,
+                    MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED,
+                )
+            }
+        };
+
+    let attestation_status = match report {
+        None => MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED,
+        Some(report) => match IngestReportVerifier::from(fog_report_verifier)
+            .validate_ingest_ias_report(report.report.clone())
+        {
+            Ok(_) => MC_FOG_REPORT_CHECK_STATUS_PASSED,
+            Err(err) => {
+                failures.push(format!("Attestation verification failed: {:?}", err));
+                MC_FOG_REPORT_CHECK_STATUS_FAILED
+            }
+        },
+    };
+
+    let pubkey_expiry = report.map_or(0, |report| report.pubkey_expiry);
+    let expiry_status = match report {
+        None => MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED,
+        Some(_) if pubkey_expiry > current_block_height => MC_FOG_REPORT_CHECK_STATUS_PASSED,
+        Some(_) => {
+            failures.push(format!(
+                "Fog pubkey expired at block {}, current block height is {}",
+                pubkey_expiry, current_block_height
+            ));
+            MC_FOG_REPORT_CHECK_STATUS_FAILED
+        }
+    };
+
+    Ok(ReportResponseValidation {
+        result: McFogReportValidationResult {
+            report_id_found: report.is_some(),
+            pubkey_expiry,
+            certificate_chain_status,
+            report_signature_status,
+            attestation_status,
+            expiry_status,
+        },
+        failures,
+    })
+}
+
+/// Validates one fog report response for `recipient` with the checks that
+/// `mc_fog_resolver_get_fog_pubkey` runs, and reports the outcome of each
+/// check instead of stopping at the first failure. This is meant for
+/// diagnosing recipients that cannot be sent to.
+///
+/// The certificate chain is checked first; the signature over the reports is
+/// only checked once the chain has passed.
+///
+/// `out_details` is set to a description of each failed check, or to null if
+/// every check passed.
+///
+/// # Preconditions
+///
+/// * `report_response` - must be a valid binary-serialized
+///   `report.ReportResponse` Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `recipient` has no fog info.
+#[no_mangle]
+pub extern "C" fn mc_fog_report_response_validate(
+    report_response: FfiRefPtr<McBuffer>,
+    fog_report_verifier: FfiRefPtr<McVerifier>,
+    recipient: FfiRefPtr<McPublicAddress>,
+    current_block_height: u64,
+    out_result: FfiMutPtr<McFogReportValidationResult>,
+    out_details: FfiOptMutPtr<FfiOptOwnedStr>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let report_response: ReportResponse = mc_util_serial::decode(report_response.as_slice())?;
+        let recipient = PublicAddress::try_from_ffi(&recipient)?;
+
+        let validation = validate_report_response(
+            &report_response,
+            &fog_report_verifier,
+            &recipient,
+            current_block_height,
+        )?;
+        let details = (!validation.failures.is_empty()).then(|| validation.failures.join("\n"));
+        let details = FfiOptOwnedStr::ffi_try_from(details.as_deref())?;
+
+        *out_result.into_mut() = validation.result;
+        if let Some(out_details) = out_details.into_mut() {
+            *out_details = details;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_account_keys::AccountKey;
+    use mc_attest_core::VerificationReport;
+    use mc_fog_report_types::Report;
+    use mc_fog_sig_report::Signer as ReportSigner;
+    use mc_fog_types::{
+        ledger::OutputResult,
+        view::{DecommissionedIngestInvocation, RngRecord, TxOutSearchResult},
+    };
+    use mc_transaction_core::{
+        membership_proofs::{hash_leaf, Range},
+        tx::TxOutMembershipElement,
+        Amount, BlockVersion, TokenId,
+    };
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn view_private_key(seed: u64) -> RistrettoPrivate {
+        RistrettoPrivate::from_random(&mut ChaCha20Rng::seed_from_u64(seed))
+    }
+
+    fn kex_rng_pubkey(seed: u64) -> KexRngPubkey {
+        let ingest_private_key = RistrettoPrivate::from_random(&mut ChaCha20Rng::seed_from_u64(
+            seed.wrapping_add(1 << 32),
+        ));
+        KexRngPubkey {
+            public_key: RistrettoPublic::from(&ingest_private_key)
+                .to_bytes()
+                .to_vec(),
+            version: 1,
+        }
+    }
+
+    fn fog_rng(seed: u64) -> VersionedKexRng {
+        VersionedKexRng::try_from_kex_pubkey(&kex_rng_pubkey(seed), &view_private_key(seed))
+            .unwrap()
+    }
+
+    fn search_result(search_key: &[u8], result_code: TxOutSearchResultCode) -> TxOutSearchResult {
+        TxOutSearchResult {
+            search_key: search_key.to_vec(),
+            result_code: result_code as u32,
+            ciphertext: vec![0; 32],
+        }
+    }
+
+    #[test]
+    fn peeking_many_outputs_matches_single_steps() {
+        let fog_rng = fog_rng(0);
+        let mut stepped_fog_rng = fog_rng.clone();
+        let stepped_outputs: Vec<Vec<u8>> = (0..5)
+            .map(|_| {
+                let output = stepped_fog_rng.peek().to_vec();
+                stepped_fog_rng.advance();
+                output
+            })
+            .collect();
+
+        assert_eq!(peek_outputs(&fog_rng, 5), stepped_outputs);
+        assert_eq!(fog_rng.peek(), stepped_outputs[0].as_slice());
+        assert!(peek_outputs(&fog_rng, 0).is_empty());
+    }
+
+    #[test]
+    fn advancing_by_count_matches_repeated_advance() {
+        let mut fog_rng = fog_rng(0);
+        let mut stepped_fog_rng = fog_rng.clone();
+        for _ in 0..5 {
+            stepped_fog_rng.advance();
+        }
+
+        advance_by(&mut fog_rng, 5);
+        assert_eq!(fog_rng.peek(), stepped_fog_rng.peek());
+        assert_eq!(fog_rng.index(), stepped_fog_rng.index());
+    }
+
+    #[test]
+    fn overflowing_peek_length_is_an_error() {
+        assert_eq!(peek_outputs_len(5, 32).unwrap(), 160);
+        assert!(matches!(
+            peek_outputs_len(usize::MAX, 32),
+            Err(LibMcError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            peek_outputs_len(ssize_t::MAX as usize + 1, 1),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    fn view_query(fog_rngs: &[VersionedKexRng]) -> FogViewQuery {
+        FogViewQuery {
+            start_from_user_event_id: 0,
+            start_from_block_index: 0,
+            fog_rngs: fog_rngs
+                .iter()
+                .map(|fog_rng| (fog_rng.clone(), 4))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn view_query_requests_search_keys_of_every_rng() {
+        let fog_rngs = [fog_rng(0), fog_rng(1)];
+        let query = view_query(&fog_rngs);
+
+        let request = query.request();
+        assert_eq!(request.get_txos.len(), 8);
+        assert_eq!(request.get_txos[0], fog_rngs[0].peek());
+        assert_eq!(request.get_txos[4], fog_rngs[1].peek());
+    }
+
+    #[test]
+    fn view_query_does_not_advance_past_missing_keys() {
+        let fog_rngs = [fog_rng(0)];
+        let mut query = view_query(&fog_rngs);
+        let mut response = QueryResponse::default();
+        response.tx_out_search_results.push(search_result(
+            fog_rngs[0].peek(),
+            TxOutSearchResultCode::NotFound,
+        ));
+
+        let result = query
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+        assert!(result.tx_outs.is_empty());
+        assert_eq!(query.fog_rngs[0].0.peek(), fog_rngs[0].peek());
+    }
+
+    #[test]
+    fn view_query_failed_response_leaves_rngs_unchanged() {
+        let fog_rngs = [fog_rng(0)];
+        let mut query = view_query(&fog_rngs);
+        let mut response = QueryResponse::default();
+        // The ciphertext is garbage, so decrypting the found output fails.
+        response.tx_out_search_results.push(search_result(
+            fog_rngs[0].peek(),
+            TxOutSearchResultCode::Found,
+        ));
+
+        assert!(query
+            .process_response(&response, &view_private_key(0))
+            .is_err());
+        assert_eq!(query.fog_rngs[0].0.peek(), fog_rngs[0].peek());
+    }
+
+    fn report_response(pubkey_expiries: &[u64]) -> ReportResponse {
+        ReportResponse {
+            reports: pubkey_expiries
+                .iter()
+                .enumerate()
+                .map(|(index, pubkey_expiry)| Report {
+                    fog_report_id: index.to_string(),
+                    report: VerificationReport::default(),
+                    pubkey_expiry: *pubkey_expiry,
+                })
+                .collect(),
+            chain: vec![vec![1, 2, 3]],
+            signature: vec![4, 5, 6],
+        }
+    }
+
+    #[test]
+    fn report_responses_round_trip_through_stored_cache() {
+        let mut fog_report_cache = FogReportCache::new(Verifier::default());
+        fog_report_cache.add_report_response(
+            "fog://fog.example.com".to_owned(),
+            report_response(&[10, 20]),
+        );
+        let stored = StoredFogReportCache::from(&fog_report_cache);
+
+        let decoded: StoredFogReportCache =
+            mc_util_serial::decode(&mc_util_serial::encode(&stored)).unwrap();
+        assert_eq!(decoded, stored);
+        let entry = &decoded.entries[0];
+        assert_eq!(entry.report_url, "fog://fog.example.com");
+        let decoded_response: ReportResponse =
+            mc_util_serial::decode(&entry.report_response).unwrap();
+        assert_eq!(decoded_response, report_response(&[10, 20]));
+    }
+
+    #[test]
+    fn report_url_is_stale_once_any_report_expires() {
+        let mut fog_report_cache = FogReportCache::new(Verifier::default());
+        fog_report_cache.add_report_response(
+            "fog://fog.example.com".to_owned(),
+            report_response(&[10, 20]),
+        );
+        fog_report_cache
+            .add_report_response("fog://empty.example.com".to_owned(), report_response(&[]));
+
+        assert_eq!(
+            fog_report_cache.stale_report_urls(9),
+            vec!["fog://empty.example.com".to_owned()]
+        );
+        assert_eq!(
+            fog_report_cache.stale_report_urls(10),
+            vec![
+                "fog://empty.example.com".to_owned(),
+                "fog://fog.example.com".to_owned()
+            ]
+        );
+    }
+
+    /// A report response with one report expiring at block 10, signed by the
+    /// leaf of a test certificate chain, and the chain's certificates.
+    fn signed_report_response() -> (ReportResponse, Vec<Vec<u8>>) {
+        let (chain_pem, leaf_keypair) = mc_crypto_x509_test_vectors::ok_rsa_chain_25519_leaf();
+        let chain = pem::parse_many(chain_pem)
+            .unwrap()
+            .into_iter()
+            .map(|pem| pem.contents)
+            .collect::<Vec<_>>();
+        let reports = vec![Report {
+            fog_report_id: "".to_owned(),
+            report: VerificationReport::default(),
+            pubkey_expiry: 10,
+        }];
+        let signature = leaf_keypair.sign_reports(&reports).unwrap();
+        let report_response = ReportResponse {
+            reports,
+            chain: chain.clone(),
+            signature: signature.as_ref().to_vec(),
+        };
+        (report_response, chain)
+    }
+
+    fn self_issued_spki(chain: &[Vec<u8>]) -> Vec<u8> {
+        chain
+            .iter()
+            .map(|der| x509_signature::parse_certificate(der).unwrap())
+            .find(|cert| cert.check_self_issued().is_ok())
+            .unwrap()
+            .subject_public_key_info()
+            .spki()
+            .to_vec()
+    }
+
+    fn fog_recipient(report_id: &str, authority_spki: &[u8]) -> PublicAddress {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        AccountKey::new_with_fog(
+            &RistrettoPrivate::from_random(&mut rng),
+            &RistrettoPrivate::from_random(&mut rng),
+            "fog://fog.example.com",
+            report_id.to_owned(),
+            authority_spki,
+        )
+        .default_subaddress()
+    }
+
+    #[test]
+    fn validate_accepts_valid_report_response() {
+        let (report_response, chain) = signed_report_response();
+        let recipient = fog_recipient("", &self_issued_spki(&chain));
+
+        let validation =
+            validate_report_response(&report_response, &Verifier::default(), &recipient, 9)
+                .unwrap();
+        let result = validation.result;
+
+        assert!(result.report_id_found);
+        assert_eq!(result.pubkey_expiry, 10);
+        assert_eq!(
+            result.certificate_chain_status,
+            MC_FOG_REPORT_CHECK_STATUS_PASSED
+        );
+        assert_eq!(
+            result.report_signature_status,
+            MC_FOG_REPORT_CHECK_STATUS_PASSED
+        );
+        assert_eq!(result.expiry_status, MC_FOG_REPORT_CHECK_STATUS_PASSED);
+        // The test report carries no attestation evidence.
+        assert_eq!(result.attestation_status, MC_FOG_REPORT_CHECK_STATUS_FAILED);
+        assert_eq!(validation.failures.len(), 1);
+    }
+
+    #[test]
+    fn validate_reports_missing_report_id() {
+        let (report_response, chain) = signed_report_response();
+        let recipient = fog_recipient("other", &self_issued_spki(&chain));
+
+        let result =
+            validate_report_response(&report_response, &Verifier::default(), &recipient, 9)
+                .unwrap()
+                .result;
+
+        assert!(!result.report_id_found);
+        assert_eq!(result.pubkey_expiry, 0);
+        assert_eq!(
+            result.attestation_status,
+            MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED
+        );
+        assert_eq!(result.expiry_status, MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED);
+    }
+
+    #[test]
+    fn validate_rejects_wrong_authority() {
+        let (report_response, chain) = signed_report_response();
+        let root_spki = self_issued_spki(&chain);
+        let other_spki = chain
+            .iter()
+            .map(|der| {
+                x509_signature::parse_certificate(der)
+                    .unwrap()
+                    .subject_public_key_info()
+                    .spki()
+                    .to_vec()
+            })
+            .find(|spki| *spki != root_spki)
+            .unwrap();
+        let recipient = fog_recipient("", &other_spki);
+
+        let result =
+            validate_report_response(&report_response, &Verifier::default(), &recipient, 9)
+                .unwrap()
+                .result;
+
+        assert_eq!(
+            result.certificate_chain_status,
+            MC_FOG_REPORT_CHECK_STATUS_FAILED
+        );
+        assert_eq!(
+            result.report_signature_status,
+            MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED
+        );
+    }
+
+    #[test]
+    fn validate_rejects_expired_pubkey() {
+        let (report_response, chain) = signed_report_response();
+        let recipient = fog_recipient("", &self_issued_spki(&chain));
+
+        let result =
+            validate_report_response(&report_response, &Verifier::default(), &recipient, 10)
+                .unwrap()
+                .result;
+
+        assert_eq!(result.pubkey_expiry, 10);
+        assert_eq!(
+            result.certificate_chain_status,
+            MC_FOG_REPORT_CHECK_STATUS_PASSED
+        );
+        assert_eq!(result.expiry_status, MC_FOG_REPORT_CHECK_STATUS_FAILED);
+    }
+
+    #[test]
+    fn validate_rejects_reversed_chain() {
+        let (mut report_response, chain) = signed_report_response();
+        report_response.chain.reverse();
+        let recipient = fog_recipient("", &self_issued_spki(&chain));
+
+        let result =
+            validate_report_response(&report_response, &Verifier::default(), &recipient, 9)
+                .unwrap()
+                .result;
+
+        assert_eq!(
+            result.certificate_chain_status,
+            MC_FOG_REPORT_CHECK_STATUS_FAILED
+        );
+        assert_eq!(
+            result.report_signature_status,
+            MC_FOG_REPORT_CHECK_STATUS_NOT_CHECKED
+        );
+    }
+
+    #[test]
+    fn equivalent_report_urls_are_added_once() {
+        let mut report_urls = McFogReportUrls::new();
+        for report_url in [
+            "fog://fog.example.com",
+            "fog://fog.example.com:443",
+            "fog://fog.example.com/",
+            "fog://fog.example.com:443/",
+            "",
+        ] {
+            add_report_url(&mut report_urls, report_url).unwrap();
+        }
+
+        assert_eq!(
+            report_urls,
+            vec![FogUri::from_str("fog://fog.example.com")
+                .unwrap()
+                .to_string()]
+        );
+
+        add_report_url(&mut report_urls, "fog://other.example.com").unwrap();
+        assert_eq!(report_urls.len(), 2);
+        assert!(matches!(
+            add_report_url(&mut report_urls, "https://fog.example.com"),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn missing_report_urls_exclude_cached_responses() {
+        let mut fog_report_cache = FogReportCache::new(Verifier::default());
+        fog_report_cache.add_report_response(
+            FogUri::from_str("fog://cached.example.com")
+                .unwrap()
+                .to_string(),
+            report_response(&[10]),
+        );
+        let mut report_urls = McFogReportUrls::new();
+        add_report_url(&mut report_urls, "fog://cached.example.com:443/").unwrap();
+        add_report_url(&mut report_urls, "fog://missing.example.com").unwrap();
+
+        assert_eq!(
+            missing_report_urls(&fog_report_cache, &report_urls),
+            vec![FogUri::from_str("fog://missing.example.com")
+                .unwrap()
+                .to_string()]
+        );
+        assert!(missing_report_urls(&fog_report_cache, &McFogReportUrls::new()).is_empty());
+    }
+
+    fn rng_record(ingest_invocation_id: i64, seed: u64) -> RngRecord {
+        RngRecord {
+            ingest_invocation_id,
+            pubkey: kex_rng_pubkey(seed),
+            start_block: 100,
+        }
+    }
+
+    #[test]
+    fn rng_set_adds_one_rng_per_ingest_key() {
+        let mut fog_rng_set = FogRngSet {
+            entries: BTreeMap::new(),
+        };
+        let response = QueryResponse {
+            rng_records: vec![rng_record(1, 1), rng_record(2, 2), rng_record(3, 1)],
+            ..Default::default()
+        };
+
+        fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+        assert_eq!(fog_rng_set.entries.len(), 2);
+        assert_eq!(fog_rng_set.query(4, 0, 0).fog_rngs.len(), 2);
+
+        // Records already in the set are not added again.
+        fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+        assert_eq!(fog_rng_set.entries.len(), 2);
+    }
+
+    #[test]
+    fn rng_set_round_trips_through_stored_form() {
+        let mut fog_rng_set = FogRngSet {
+            entries: BTreeMap::new(),
+        };
+        let response = QueryResponse {
+            rng_records: vec![rng_record(1, 1), rng_record(2, 2)],
+            decommissioned_ingest_invocations: vec![DecommissionedIngestInvocation {
+                ingest_invocation_id: 2,
+                last_ingested_block: 150,
+            }],
+            ..Default::default()
+        };
+        fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+
+        let encoded = mc_util_serial::encode(&StoredFogRngSet::from(&fog_rng_set));
+        let decoded =
+            FogRngSet::try_from(mc_util_serial::decode::<StoredFogRngSet>(&encoded).unwrap())
+                .unwrap();
+
+        assert_eq!(decoded.entries.len(), fog_rng_set.entries.len());
+        for (index, entry) in fog_rng_set.entries.values().enumerate() {
+            let decoded_entry = decoded.entry(index);
+            assert_eq!(decoded_entry.fog_rng.peek(), entry.fog_rng.peek());
+            assert_eq!(
+                decoded_entry.ingest_invocation_id,
+                entry.ingest_invocation_id
+            );
+            assert_eq!(decoded_entry.start_block, entry.start_block);
+            assert_eq!(decoded_entry.last_ingested_block, entry.last_ingested_block);
+            assert_eq!(decoded_entry.exhausted, entry.exhausted);
+        }
+        assert!(fog_rng_set
+            .entries
+            .values()
+            .any(|entry| entry.last_ingested_block == Some(150)));
+    }
+
+    #[test]
+    fn decommissioned_rngs_are_exhausted_once_fully_processed() {
+        let mut fog_rng_set = FogRngSet {
+            entries: BTreeMap::new(),
+        };
+        let response = QueryResponse {
+            rng_records: vec![rng_record(7, 1)],
+            decommissioned_ingest_invocations: vec![DecommissionedIngestInvocation {
+                ingest_invocation_id: 7,
+                last_ingested_block: 150,
+            }],
+            highest_processed_block_count: 150,
+            ..Default::default()
+        };
+        fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+        assert!(!fog_rng_set.entry(0).exhausted);
+
+        let response = QueryResponse {
+            highest_processed_block_count: 151,
+            tx_out_search_results: vec![search_result(
+                fog_rng_set.entry(0).fog_rng.peek(),
+                TxOutSearchResultCode::NotFound,
+            )],
+            ..Default::default()
+        };
+        fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .unwrap();
+        assert!(fog_rng_set.entry(0).exhausted);
+        assert!(fog_rng_set.query(4, 0, 0).fog_rngs.is_empty());
+    }
+
+    #[test]
+    fn failed_rng_set_response_leaves_set_unchanged() {
+        let mut fog_rng_set = FogRngSet {
+            entries: BTreeMap::new(),
+        };
+        fog_rng_set
+            .process_response(
+                &QueryResponse {
+                    rng_records: vec![rng_record(1, 1)],
+                    ..Default::default()
+                },
+                &view_private_key(0),
+            )
+            .unwrap();
+
+        let new_fog_rng =
+            VersionedKexRng::try_from_kex_pubkey(&kex_rng_pubkey(2), &view_private_key(0)).unwrap();
+        let response = QueryResponse {
+            rng_records: vec![rng_record(2, 2)],
+            decommissioned_ingest_invocations: vec![DecommissionedIngestInvocation {
+                ingest_invocation_id: 1,
+                last_ingested_block: 150,
+            }],
+            tx_out_search_results: vec![search_result(
+                new_fog_rng.peek(),
+                TxOutSearchResultCode::Found,
+            )],
+            ..Default::default()
+        };
+
+        assert!(fog_rng_set
+            .process_response(&response, &view_private_key(0))
+            .is_err());
+        assert_eq!(fog_rng_set.entries.len(), 1);
+        assert_eq!(fog_rng_set.entry(0).last_ingested_block, None);
+    }
+
+    fn tx_out(rng: &mut ChaCha20Rng) -> TxOut {
+        TxOut::new(
+            BlockVersion::MAX,
+            Amount::new(10, TokenId::from(0)),
+            &AccountKey::random(rng).default_subaddress(),
+            &RistrettoPrivate::from_random(rng),
+            Default::default(),
+        )
+        .unwrap()
+    }
+
+    /// The membership proof of leaf `index` in a tree of two leaves.
+    fn membership_proof(index: u64, leaf_hashes: [[u8; 32]; 2]) -> TxOutMembershipProof {
+        let elements = [index, 1 - index]
+            .iter()
+            .map(|leaf| {
+                TxOutMembershipElement::new(
+                    Range::new(*leaf, *leaf).unwrap(),
+                    leaf_hashes[*leaf as usize],
+                )
+            })
+            .collect();
+        TxOutMembershipProof::new(index, 1, elements)
+    }
+
+    fn output_result(index: u64, output: TxOut, proof: TxOutMembershipProof) -> OutputResult {
+        OutputResult {
+            index,
+            result_code: OutputResultCode::Exists as u32,
+            output,
+            proof,
+        }
+    }
+
+    /// A request for outputs 0 and 1 of a two-output ledger, and its response.
+    fn get_outputs(rng: &mut ChaCha20Rng) -> (GetOutputsRequest, GetOutputsResponse) {
+        let outputs = [tx_out(rng), tx_out(rng)];
+        let leaf_hashes = [hash_leaf(&outputs[0]), hash_leaf(&outputs[1])];
+        let request = GetOutputsRequest {
+            indices: vec![0, 1],
+            merkle_root_block: 5,
+        };
+        let response = GetOutputsResponse {
+            results: outputs
+                .iter()
+                .enumerate()
+                .map(|(index, output)| {
+                    output_result(
+                        index as u64,
+                        output.clone(),
+                        membership_proof(index as u64, leaf_hashes),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        (request, response)
+    }
+
+    #[test]
+    fn outputs_from_valid_response_are_returned_in_request_order() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (request, response) = get_outputs(&mut rng);
+
+        let outputs = outputs_from_response(&request, response.clone()).unwrap();
+        assert_eq!(outputs.len(), 2);
+        for ((output, proof), result) in outputs.iter().zip(&response.results) {
+            assert_eq!(*output, result.output);
+            assert_eq!(*proof, result.proof);
+        }
+    }
+
+    #[test]
+    fn outputs_response_with_wrong_result_count_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (request, mut response) = get_outputs(&mut rng);
+        response.results.pop();
+
+        assert!(matches!(
+            outputs_from_response(&request, response),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn outputs_response_in_wrong_index_order_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (request, mut response) = get_outputs(&mut rng);
+        response.results.reverse();
+
+        assert!(matches!(
+            outputs_from_response(&request, response),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn outputs_response_with_missing_output_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (request, mut response) = get_outputs(&mut rng);
+        response.results[1].result_code = OutputResultCode::DoesNotExist as u32;
+
+        assert!(matches!(
+            outputs_from_response(&request, response),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn outputs_response_with_mismatched_merkle_roots_is_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (request, mut response) = get_outputs(&mut rng);
+        // A proof that is valid for output 1, but in a tree with another output
+        // 0, so it implies a different root than the proof of output 0.
+        let other_leaf_hashes = [
+            hash_leaf(&tx_out(&mut rng)),
+            hash_leaf(&response.results[1].output),
+        ];
+        response.results[1].proof = membership_proof(1, other_leaf_hashes);
+
+        assert!(matches!(
+            outputs_from_response(&request, response),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    fn key_image_result(key_image_result_code: KeyImageResultCode) -> KeyImageResult {
+        KeyImageResult {
+            key_image: KeyImage::from([7u8; 32]),
+            spent_at: 42,
+            timestamp: 1_600_000_000,
+            key_image_result_code: key_image_result_code as u32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spent_key_image_results_report_where_they_were_spent() {
+        let result = McFogKeyImageResult::from(&key_image_result(KeyImageResultCode::Spent));
+        assert_eq!(result.status, MC_FOG_KEY_IMAGE_STATUS_SPENT);
+        assert_eq!(result.spent_at, 42);
+        assert_eq!(result.timestamp, 1_600_000_000);
+    }
+
+    #[test]
+    fn unspent_key_image_results_clear_spend_details() {
+        let result = McFogKeyImageResult::from(&key_image_result(KeyImageResultCode::NotSpent));
+        assert_eq!(result.status, MC_FOG_KEY_IMAGE_STATUS_NOT_SPENT);
+        assert_eq!(result.spent_at, 0);
+        assert_eq!(result.timestamp, 0);
+    }
+
+    #[test]
+    fn unknown_key_image_result_codes_are_errors() {
+        let result =
+            McFogKeyImageResult::from(&key_image_result(KeyImageResultCode::KeyImageError));
+        assert_eq!(result.status, MC_FOG_KEY_IMAGE_STATUS_ERROR);
+        assert_eq!(result.spent_at, 0);
+
+        let mut result = key_image_result(KeyImageResultCode::Spent);
+        result.key_image_result_code = u32::MAX;
+        assert_eq!(
+            McFogKeyImageResult::from(&result).status,
+            MC_FOG_KEY_IMAGE_STATUS_ERROR
+        );
+    }
+
+    #[test]
+    fn key_image_responses_decode_in_order() {
+        let response = CheckKeyImagesResponse {
+            num_blocks: 100,
+            results: vec![
+                key_image_result(KeyImageResultCode::Spent),
+                key_image_result(KeyImageResultCode::NotSpent),
+            ],
+            ..Default::default()
+        };
+
+        let decoded: CheckKeyImagesResponse =
+            mc_util_serial::decode(&mc_util_serial::encode(&response)).unwrap();
+        assert_eq!(decoded.num_blocks, 100);
+        let statuses = decoded
+            .results
+            .iter()
+            .map(|result| McFogKeyImageResult::from(result).status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                MC_FOG_KEY_IMAGE_STATUS_SPENT,
+                MC_FOG_KEY_IMAGE_STATUS_NOT_SPENT
+            ]
+        );
+    }
+}
@@ -0,0 +1,834 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+use crate::{
+    common::*,
+    encodings::{TransferPayloadContents, TransferPayloadEntropy},
+    fog::{McFogResolver, SdkFogResolver},
+    keys::{McAccountKey, McAccountKeyHandle},
+    transaction::{
+        McTransactionBuilder, McTransactionBuilderRing, McTxOutAmount, TransactionBuilderHandle,
+    },
+    LibMcError,
+};
+use bip39::{Language, Mnemonic};
+use core::convert::TryFrom;
+use mc_account_keys::{
+    AccountKey, PublicAddress, RootIdentity, DEFAULT_SUBADDRESS_INDEX, GIFT_CODE_SUBADDRESS_INDEX,
+};
+use mc_account_keys_slip10::Slip10KeyGenerator;
+use mc_api::printable::{PrintableWrapper, TransferPayload};
+use mc_crypto_keys::{CompressedRistrettoPublic, ReprBytes, RistrettoPrivate, RistrettoPublic};
+use mc_crypto_ring_signature_signer::NoKeysRingSigner;
+use mc_transaction_core::{
+    get_tx_out_shared_secret, onetime_keys::recover_onetime_private_key, tx::TxOut, Amount,
+    BlockVersion, TokenId,
+};
+use mc_transaction_std::{
+    GiftCodeCancellationMemoBuilder, GiftCodeFundingMemoBuilder, GiftCodeSenderMemoBuilder,
+    InputCredentials, MemoBuilder, ReservedSubaddresses, TransactionBuilder,
+};
+use mc_util_ffi::*;
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+/* ==== McGiftCode ==== */
+
+/// The contents of a gift code: the entropy of the single-use account holding
+/// the gift code `TxOut`, the public key of that `TxOut`, and a note from the
+/// sender. This is what the b58-encoded `TransferPayload` carries.
+pub struct GiftCode {
+    /// BIP39 entropy, or 32-byte legacy root entropy when `is_root_entropy`.
+    entropy: Zeroizing<Vec<u8>>,
+    is_root_entropy: bool,
+    tx_out_public_key: CompressedRistrettoPublic,
+    memo: String,
+}
+
+impl GiftCode {
+    fn account_key(&self) -> Result<AccountKey, LibMcError> {
+        gift_code_account_key(&self.entropy, self.is_root_entropy)
+    }
+
+    /// Checks that `tx_out` is the gift code `TxOut` and returns its amount.
+    fn tx_out_amount(&self, tx_out: &TxOut) -> Result<Amount, LibMcError> {
+        if tx_out.public_key != self.tx_out_public_key {
+            return Err(LibMcError::InvalidInput(
+                "TxOut public key does not match the gift code".to_owned(),
+            ));
+        }
+        let account_key = self.account_key()?;
+        let public_key = RistrettoPublic::try_from(&tx_out.public_key)?;
+        onetime_private_key(&account_key, tx_out)?;
+
+        let shared_secret = get_tx_out_shared_secret(account_key.view_private_key(), &public_key);
+        let (amount, _) = tx_out.masked_amount.get_value(&shared_secret)?;
+        Ok(amount)
+    }
+
+    /// Builds a transaction spending the gift code `TxOut` in `ring` at
+    /// `real_index`. `add_outputs` is given the builder and the value left
+    /// over after paying the fee.
+    #[allow(clippy::too_many_arguments)]
+    fn build_spend<RNG: CryptoRng + RngCore>(
+        &self,
+        ring: &McTransactionBuilderRing,
+        real_index: usize,
+        fee: u64,
+        tombstone_block: u64,
+        fog_resolver: SdkFogResolver,
+        memo_builder: Box<dyn MemoBuilder + Sync + Send>,
+        block_version: u32,
+        rng: &mut RNG,
+        add_outputs: impl FnOnce(
+            &mut TransactionBuilder<SdkFogResolver>,
+            Amount,
+            &mut RNG,
+        ) -> Result<(), LibMcError>,
+    ) -> Result<Vec<u8>, LibMcError> {
+        let (gift_code_tx_out, _) = ring.get(real_index).ok_or_else(|| {
+            LibMcError::InvalidInput("real_index not in bounds of ring".to_owned())
+        })?;
+        let amount = self.tx_out_amount(gift_code_tx_out)?;
+        let value = amount.value.checked_sub(fee).ok_or_else(|| {
+            LibMcError::InvalidInput("Gift code value is less than the fee".to_owned())
+        })?;
+
+        let block_version = BlockVersion::try_from(block_version)?;
+        let fee_amount = Amount::new(fee, amount.token_id);
+        let mut transaction_builder = TransactionBuilder::new_with_box(
+            block_version,
+            fee_amount,
+            fog_resolver,
+            memo_builder,
+        )?;
+        transaction_builder.set_tombstone_block(tombstone_block);
+
+        let account_key = self.account_key()?;
+        let onetime_private_key = onetime_private_key(&account_key, gift_code_tx_out)?;
+        let input_credential = InputCredentials::new(
+            ring.iter().map(|element| element.0.clone()).collect(),
+            ring.iter().map(|element| element.1.clone()).collect(),
+            real_index,
+            onetime_private_key,
+            account_key.view_private_key().clone(),
+        )
+        .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        transaction_builder.add_input(input_credential);
+
+        add_outputs(
+            &mut transaction_builder,
+            Amount::new(value, amount.token_id),
+            rng,
+        )?;
+
+        let tx = transaction_builder
+            .build(&NoKeysRingSigner {}, rng)
+            .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+        Ok(mc_util_serial::encode(&tx))
+    }
+}
+
+impl From<&GiftCode> for PrintableWrapper {
+    fn from(src: &GiftCode) -> Self {
+        let mut transfer_payload = TransferPayload::new();
+        if src.is_root_entropy {
+            transfer_payload.set_root_entropy(src.entropy.to_vec());
+        } else {
+            transfer_payload.set_bip39_entropy(src.entropy.to_vec());
+        }
+        transfer_payload.set_tx_out_public_key((&src.tx_out_public_key).into());
+        transfer_payload.set_memo(src.memo.clone());
+
+        let mut printable_wrapper = PrintableWrapper::new();
+        printable_wrapper.set_transfer_payload(transfer_payload);
+        printable_wrapper
+    }
+}
+
+impl TryFrom<&PrintableWrapper> for GiftCode {
+    type Error = LibMcError;
+
+    fn try_from(src: &PrintableWrapper) -> Result<Self, LibMcError> {
+        if !src.has_transfer_payload() {
+            return Err(LibMcError::InvalidInput(
+                "PrintableWrapper does not contain a TransferPayload".to_owned(),
+            ));
+        }
+        let transfer_payload = TransferPayloadContents::try_from(src.get_transfer_payload())?;
+
+        Ok(Self {
+            entropy: Zeroizing::new(transfer_payload.entropy.bytes().to_vec()),
+            is_root_entropy: matches!(transfer_payload.entropy, TransferPayloadEntropy::Root(_)),
+            tx_out_public_key: transfer_payload.tx_out_public_key,
+            memo: transfer_payload.memo.to_owned(),
+        })
+    }
+}
+
+pub type McGiftCode = GiftCode;
+impl_into_ffi!(GiftCode);
+
+#[no_mangle]
+pub extern "C" fn mc_gift_code_free(gift_code: FfiOptOwnedPtr<McGiftCode>) {
+    ffi_boundary(|| {
+        let _ = gift_code;
+    })
+}
+
+/// Creates a transaction builder whose change output will carry a gift code
+/// funding memo. Inputs are then added with `mc_transaction_builder_add_input`
+/// and the outputs with `mc_transaction_builder_fund_gift_code`.
+///
+/// `current_block_height` is used as by `mc_transaction_builder_create`.
+///
+/// # Preconditions
+///
+/// * `funding_note` - must be a nul-terminated C string containing at most 53
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_funding_transaction_builder_create(
+    fee: u64,
+    token_id: u64,
+    tombstone_block: u64,
+    current_block_height: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    funding_note: FfiStr,
+    block_version: u32,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McTransactionBuilder> {
+    ffi_boundary_with_error(out_error, || {
+        let fog_resolver = fog_resolver_from_ffi(&fog_resolver)?;
+        let funding_note = <&str>::try_from_ffi(funding_note).expect("funding_note is invalid");
+        let memo_builder = GiftCodeFundingMemoBuilder::new(funding_note)
+            .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+
+        let block_version = BlockVersion::try_from(block_version)?;
+        let fee_amount = Amount::new(fee, TokenId::from(token_id));
+        let transaction_builder = TransactionBuilder::new_with_box(
+            block_version,
+            fee_amount,
+            fog_resolver.clone(),
+            Box::new(memo_builder),
+        )?;
+        Ok(TransactionBuilderHandle::new(
+            transaction_builder,
+            fog_resolver,
+            tombstone_block,
+            current_block_height,
+        ))
+    })
+}
+
+/// Generates fresh entropy for a new gift code, sends `amount` to the gift
+/// code subaddress of the new gift code account, and sends the change back to
+/// the funder's change subaddress.
+///
+/// The change is the value of the inputs added to the builder, less `amount`
+/// and the fee, so every input must be added before calling this. The funding
+/// transaction is then produced by `mc_transaction_builder_build`.
+///
+/// # Preconditions
+///
+/// * `transaction_builder` - must have been created by
+///   `mc_gift_code_funding_transaction_builder_create` and not previously
+///   consumed by a call to `build`.
+/// * `account_key` - must be a valid account key.
+/// * `memo` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::FogPubkey` - the change subaddress's fog pubkey expires at or
+///   before the `current_block_height` the builder was created with.
+/// * `LibMcError::InvalidInput` - the inputs do not cover `amount` and the
+///   fee.
+#[no_mangle]
+pub extern "C" fn mc_transaction_builder_fund_gift_code(
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    account_key: FfiRefPtr<McAccountKey>,
+    amount: u64,
+    memo: FfiStr,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McGiftCode> {
+    ffi_boundary_with_error(out_error, || {
+        let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
+        fund_gift_code(
+            transaction_builder,
+            &ReservedSubaddresses::from(&account_key),
+            amount,
+            memo,
+            rng_callback,
+        )
+    })
+}
+
+/// # Preconditions
+///
+/// * `transaction_builder` - must have been created by
+///   `mc_gift_code_funding_transaction_builder_create` and not previously
+///   consumed by a call to `build`.
+/// * `memo` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::FogPubkey` - the change subaddress's fog pubkey expires at or
+///   before the `current_block_height` the builder was created with.
+/// * `LibMcError::InvalidInput` - the inputs do not cover `amount` and the
+///   fee.
+#[no_mangle]
+pub extern "C" fn mc_transaction_builder_fund_gift_code_with_handle(
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    amount: u64,
+    memo: FfiStr,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McGiftCode> {
+    ffi_boundary_with_error(out_error, || {
+        fund_gift_code(
+            transaction_builder,
+            account_key_handle.reserved_subaddresses(),
+            amount,
+            memo,
+            rng_callback,
+        )
+    })
+}
+
+fn fund_gift_code(
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    change_destination: &ReservedSubaddresses,
+    amount: u64,
+    memo: FfiStr,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+) -> Result<GiftCode, LibMcError> {
+    let transaction_builder = transaction_builder.into_mut();
+    transaction_builder.apply_fog_pubkey_expiry(&change_destination.change_subaddress)?;
+    let token_id = transaction_builder.builder_mut().get_fee_token_id();
+    let fee = transaction_builder.builder_mut().get_fee();
+    let input_value = transaction_builder.input_value(token_id)?;
+    let change_amount = input_value
+        .checked_sub(amount)
+        .and_then(|value| value.checked_sub(fee))
+        .ok_or_else(|| {
+            LibMcError::InvalidInput(format!(
+                "Inputs of {} do not cover the gift code amount {} and fee {}",
+                input_value, amount, fee
+            ))
+        })?;
+    let transaction_builder = transaction_builder.builder_mut();
+    let memo = String::try_from_ffi(memo).expect("memo is invalid");
+    let mut rng = SdkRng::from_ffi(rng_callback);
+
+    let mut entropy = Zeroizing::new(vec![0u8; 32]);
+    rng.fill_bytes(&mut entropy);
+    let gift_code_destination =
+        ReservedSubaddresses::from(&gift_code_account_key(&entropy, false)?);
+
+    let tx_out_context = transaction_builder.add_gift_code_output(
+        Amount::new(amount, token_id),
+        &gift_code_destination,
+        &mut rng,
+    )?;
+    transaction_builder.add_change_output(
+        Amount::new(change_amount, token_id),
+        change_destination,
+        &mut rng,
+    )?;
+
+    Ok(GiftCode {
+        entropy,
+        is_root_entropy: false,
+        tx_out_public_key: tx_out_context.tx_out.public_key,
+        memo,
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_gift_code_to_b58(gift_code: FfiRefPtr<McGiftCode>) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        let encoded = PrintableWrapper::from(&*gift_code)
+            .b58_encode()
+            .expect("gift_code could not be encoded as base-58");
+        FfiOwnedStr::ffi_try_from(encoded)
+            .expect("Resulting encoded string could not be converted to a C string")
+    })
+}
+
+/// # Preconditions
+///
+/// * `b58_encoded_string` - must be a nul-terminated C string containing valid
+///   UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::MalformedKey`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_from_b58(
+    b58_encoded_string: FfiStr,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McGiftCode> {
+    ffi_boundary_with_error(out_error, || {
+        let b58_encoded_string =
+            String::try_from_ffi(b58_encoded_string).expect("b58_encoded_string is invalid");
+        let printable_wrapper = PrintableWrapper::b58_decode(b58_encoded_string)?;
+        GiftCode::try_from(&printable_wrapper)
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_tx_out_public_key` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_gift_code_get_tx_out_public_key(
+    gift_code: FfiRefPtr<McGiftCode>,
+    out_tx_out_public_key: FfiMutPtr<McMutableBuffer>,
+) -> bool {
+    ffi_boundary(|| {
+        out_tx_out_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_tx_out_public_key length is insufficient")
+            .copy_from_slice(&gift_code.tx_out_public_key.to_bytes());
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_gift_code_get_memo(gift_code: FfiRefPtr<McGiftCode>) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        FfiOwnedStr::ffi_try_from(gift_code.memo.as_str())
+            .expect("memo could not be converted to a C string")
+    })
+}
+
+/// Checks that `tx_out_proto_bytes` is the gift code `TxOut` and is owned by
+/// the gift code account, then decrypts its amount.
+///
+/// # Preconditions
+///
+/// * `tx_out_proto_bytes` - must be a valid binary-serialized `external.TxOut`
+///   Protobuf.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_get_tx_out_amount(
+    gift_code: FfiRefPtr<McGiftCode>,
+    tx_out_proto_bytes: FfiRefPtr<McBuffer>,
+    out_amount: FfiMutPtr<McTxOutAmount>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let tx_out: TxOut = mc_util_serial::decode(tx_out_proto_bytes.as_slice())?;
+        let amount = gift_code.tx_out_amount(&tx_out)?;
+        *out_amount.into_mut() = McTxOutAmount::from(amount);
+        Ok(())
+    })
+}
+
+/// Builds a transaction moving the gift code value, less `fee`, to the default
+/// subaddress of `account_key`, with a gift code sender memo.
+///
+/// # Preconditions
+///
+/// * `account_key` - must be a valid account key.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `sender_note` - must be a nul-terminated C string containing at most 57
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_build_claim_transaction(
+    gift_code: FfiRefPtr<McGiftCode>,
+    account_key: FfiRefPtr<McAccountKey>,
+    ring: FfiRefPtr<McTransactionBuilderRing>,
+    real_index: usize,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    sender_note: FfiStr,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
+        build_claim_transaction(
+            &gift_code,
+            &account_key.default_subaddress(),
+            &ring,
+            real_index,
+            fee,
+            tombstone_block,
+            fog_resolver,
+            sender_note,
+            block_version,
+            rng_callback,
+        )
+    })
+}
+
+/// # Preconditions
+///
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `sender_note` - must be a nul-terminated C string containing at most 57
+///   valid UTF-8 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::Poison`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_build_claim_transaction_with_handle(
+    gift_code: FfiRefPtr<McGiftCode>,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    ring: FfiRefPtr<McTransactionBuilderRing>,
+    real_index: usize,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    sender_note: FfiStr,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        let recipient = account_key_handle
+            .subaddress(DEFAULT_SUBADDRESS_INDEX)?
+            .public_address
+            .clone();
+        build_claim_transaction(
+            &gift_code,
+            &recipient,
+            &ring,
+            real_index,
+            fee,
+            tombstone_block,
+            fog_resolver,
+            sender_note,
+            block_version,
+            rng_callback,
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_claim_transaction(
+    gift_code: &GiftCode,
+    recipient: &PublicAddress,
+    ring: &McTransactionBuilderRing,
+    real_index: usize,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    sender_note: FfiStr,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+) -> Result<Vec<u8>, LibMcError> {
+    let fog_resolver = fog_resolver_from_ffi(&fog_resolver)?;
+    let sender_note = <&str>::try_from_ffi(sender_note).expect("sender_note is invalid");
+    let memo_builder = GiftCodeSenderMemoBuilder::new(sender_note)
+        .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+    let mut rng = SdkRng::from_ffi(rng_callback);
+
+    gift_code.build_spend(
+        ring,
+        real_index,
+        fee,
+        tombstone_block,
+        fog_resolver,
+        Box::new(memo_builder),
+        block_version,
+        &mut rng,
+        |transaction_builder, amount, rng| {
+            transaction_builder.add_output(amount, recipient, rng)?;
+            Ok(())
+        },
+    )
+}
+
+/// Builds a transaction returning an unclaimed gift code, less `fee`, to the
+/// change subaddress of `account_key`, with a gift code cancellation memo.
+///
+/// # Preconditions
+///
+/// * `account_key` - must be the account key that funded the gift code.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `global_index` - must be the global TxOut index of the gift code `TxOut`.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_build_cancel_transaction(
+    gift_code: FfiRefPtr<McGiftCode>,
+    account_key: FfiRefPtr<McAccountKey>,
+    ring: FfiRefPtr<McTransactionBuilderRing>,
+    real_index: usize,
+    global_index: u64,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
+        build_cancel_transaction(
+            &gift_code,
+            &ReservedSubaddresses::from(&account_key),
+            &ring,
+            real_index,
+            global_index,
+            fee,
+            tombstone_block,
+            fog_resolver,
+            block_version,
+            rng_callback,
+        )
+    })
+}
+
+/// # Preconditions
+///
+/// * `account_key_handle` - must be the account that funded the gift code.
+/// * `ring` - `TxOut` at `real_index` must be the gift code `TxOut`.
+/// * `global_index` - must be the global TxOut index of the gift code `TxOut`.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerificationFailed`
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_gift_code_build_cancel_transaction_with_handle(
+    gift_code: FfiRefPtr<McGiftCode>,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    ring: FfiRefPtr<McTransactionBuilderRing>,
+    real_index: usize,
+    global_index: u64,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        build_cancel_transaction(
+            &gift_code,
+            account_key_handle.reserved_subaddresses(),
+            &ring,
+            real_index,
+            global_index,
+            fee,
+            tombstone_block,
+            fog_resolver,
+            block_version,
+            rng_callback,
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_cancel_transaction(
+    gift_code: &GiftCode,
+    change_destination: &ReservedSubaddresses,
+    ring: &McTransactionBuilderRing,
+    real_index: usize,
+    global_index: u64,
+    fee: u64,
+    tombstone_block: u64,
+    fog_resolver: FfiOptRefPtr<McFogResolver>,
+    block_version: u32,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+) -> Result<Vec<u8>, LibMcError> {
+    let fog_resolver = fog_resolver_from_ffi(&fog_resolver)?;
+    let memo_builder = GiftCodeCancellationMemoBuilder::new(global_index);
+    let mut rng = SdkRng::from_ffi(rng_callback);
+
+    gift_code.build_spend(
+        ring,
+        real_index,
+        fee,
+        tombstone_block,
+        fog_resolver,
+        Box::new(memo_builder),
+        block_version,
+        &mut rng,
+        |transaction_builder, amount, rng| {
+            transaction_builder.add_change_output(amount, change_destination, rng)?;
+            Ok(())
+        },
+    )
+}
+
+/// Recovers the one-time private key of a gift code `TxOut`. Gift codes are
+/// funded to the gift code account's gift code subaddress, but codes funded to
+/// its default subaddress by older clients are still accepted.
+fn onetime_private_key(
+    account_key: &AccountKey,
+    tx_out: &TxOut,
+) -> Result<RistrettoPrivate, LibMcError> {
+    let public_key = RistrettoPublic::try_from(&tx_out.public_key)?;
+    let target_key = RistrettoPublic::try_from(&tx_out.target_key)?;
+    [GIFT_CODE_SUBADDRESS_INDEX, DEFAULT_SUBADDRESS_INDEX]
+        .iter()
+        .map(|index| {
+            recover_onetime_private_key(
+                &public_key,
+                account_key.view_private_key(),
+                &account_key.subaddress_spend_private(*index),
+            )
+        })
+        .find(|onetime_private_key| RistrettoPublic::from(onetime_private_key) == target_key)
+        .ok_or_else(|| {
+            LibMcError::TransactionCrypto("TxOut is not owned by the gift code account".to_owned())
+        })
+}
+
+fn gift_code_account_key(entropy: &[u8], is_root_entropy: bool) -> Result<AccountKey, LibMcError> {
+    if is_root_entropy {
+        let root_entropy = <&[u8; 32]>::try_from(entropy).map_err(|_| {
+            LibMcError::InvalidInput("root_entropy must be 32 bytes in length".to_owned())
+        })?;
+        Ok(AccountKey::from(&RootIdentity::from(root_entropy)))
+    } else {
+        // The key is derived from the phrase rather than the entropy alone, so
+        // the gift code format fixes its language to English for every client.
+        let mnemonic = Mnemonic::from_entropy(entropy, Language::English)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid entropy: {}", err)))?;
+        Ok(AccountKey::from(mnemonic.derive_slip10_key(0)))
+    }
+}
+
+fn fog_resolver_from_ffi(
+    fog_resolver: &FfiOptRefPtr<McFogResolver>,
+) -> Result<SdkFogResolver, LibMcError> {
+    fog_resolver.as_ref().map_or_else(
+        || Ok(SdkFogResolver::default()),
+        |fog_resolver| fog_resolver.fog_resolver(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_transaction_std::EmptyMemoBuilder;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn gift_code(rng: &mut ChaCha20Rng, tx_out_public_key: CompressedRistrettoPublic) -> GiftCode {
+        let mut entropy = Zeroizing::new(vec![0u8; 32]);
+        rng.fill_bytes(&mut entropy);
+        GiftCode {
+            entropy,
+            is_root_entropy: false,
+            tx_out_public_key,
+            memo: "happy birthday".to_owned(),
+        }
+    }
+
+    fn random_public_key(rng: &mut ChaCha20Rng) -> CompressedRistrettoPublic {
+        CompressedRistrettoPublic::from(&RistrettoPublic::from(&RistrettoPrivate::from_random(rng)))
+    }
+
+    fn transaction_builder() -> TransactionBuilder<SdkFogResolver> {
+        TransactionBuilder::new_with_box(
+            BlockVersion::MAX,
+            Amount::new(10, TokenId::from(0)),
+            SdkFogResolver::default(),
+            Box::new(EmptyMemoBuilder::default()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn gift_code_round_trips_through_printable_wrapper() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let tx_out_public_key = random_public_key(&mut rng);
+        let gift_code = gift_code(&mut rng, tx_out_public_key);
+
+        let decoded = GiftCode::try_from(&PrintableWrapper::from(&gift_code)).unwrap();
+        assert_eq!(*decoded.entropy, *gift_code.entropy);
+        assert!(!decoded.is_root_entropy);
+        assert_eq!(decoded.tx_out_public_key, gift_code.tx_out_public_key);
+        assert_eq!(decoded.memo, gift_code.memo);
+    }
+
+    #[test]
+    fn gift_code_outputs_are_owned_by_the_gift_code_account() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut gift_code = gift_code(&mut rng, random_public_key(&mut rng));
+        let gift_code_destination = ReservedSubaddresses::from(&gift_code.account_key().unwrap());
+
+        let amount = Amount::new(1000, TokenId::from(0));
+        let tx_out = transaction_builder()
+            .add_gift_code_output(amount, &gift_code_destination, &mut rng)
+            .unwrap()
+            .tx_out;
+        gift_code.tx_out_public_key = tx_out.public_key;
+
+        assert_eq!(gift_code.tx_out_amount(&tx_out).unwrap(), amount);
+    }
+
+    #[test]
+    fn default_subaddress_outputs_are_still_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut gift_code = gift_code(&mut rng, random_public_key(&mut rng));
+        let default_subaddress = gift_code.account_key().unwrap().default_subaddress();
+
+        let amount = Amount::new(1000, TokenId::from(0));
+        let tx_out = transaction_builder()
+            .add_output(amount, &default_subaddress, &mut rng)
+            .unwrap()
+            .tx_out;
+        gift_code.tx_out_public_key = tx_out.public_key;
+
+        assert_eq!(gift_code.tx_out_amount(&tx_out).unwrap(), amount);
+    }
+
+    #[test]
+    fn outputs_to_other_accounts_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let mut gift_code = gift_code(&mut rng, random_public_key(&mut rng));
+        let other_account = AccountKey::random(&mut rng).default_subaddress();
+
+        let tx_out = transaction_builder()
+            .add_output(
+                Amount::new(1000, TokenId::from(0)),
+                &other_account,
+                &mut rng,
+            )
+            .unwrap()
+            .tx_out;
+        assert!(matches!(
+            gift_code.tx_out_amount(&tx_out),
+            Err(LibMcError::InvalidInput(_))
+        ));
+
+        gift_code.tx_out_public_key = tx_out.public_key;
+        assert!(matches!(
+            gift_code.tx_out_amount(&tx_out),
+            Err(LibMcError::TransactionCrypto(_))
+        ));
+    }
+}
@@ -1,9 +1,30 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-use crate::{common::*, LibMcError};
-use mc_account_keys::{AccountKey, PublicAddress, RootIdentity, ShortAddressHash};
-use mc_crypto_keys::{ReprBytes, RistrettoPrivate, RistrettoPublic};
+use crate::{
+    bip39::{McBip39Language, MC_BIP39_LANGUAGE_ENGLISH},
+    common::*,
+    crypto::ristretto_private_from_secret,
+    LibMcError,
+};
+use bip39::{Language, Mnemonic};
+use mc_account_keys::{
+    AccountKey, PublicAddress, RootIdentity, ShortAddressHash, CHANGE_SUBADDRESS_INDEX,
+    DEFAULT_SUBADDRESS_INDEX, GIFT_CODE_SUBADDRESS_INDEX,
+};
+use mc_account_keys_slip10::Slip10KeyGenerator;
+use mc_crypto_keys::{ReprBytes, RistrettoPrivate, RistrettoPublic, RistrettoSignature};
+use mc_fog_sig_authority::Verifier as AuthorityVerifier;
+use mc_transaction_std::ReservedSubaddresses;
 use mc_util_ffi::*;
+use mc_util_uri::FogUri;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryFrom,
+    os::raw::c_int,
+    str::FromStr,
+    sync::Mutex,
+};
+use zeroize::{Zeroize, Zeroizing};
 
 /* ==== Account Key ==== */
 
@@ -44,6 +65,498 @@ impl<'a> TryFromFfi<&McAccountKey<'a>> for AccountKey {
     }
 }
 
+impl<'a> McAccountKeyFogInfo<'a> {
+    pub(crate) fn add_to_account_key(
+        &self,
+        account_key: &AccountKey,
+    ) -> Result<AccountKey, LibMcError> {
+        Ok(AccountKey::new_with_fog(
+            account_key.spend_private_key(),
+            account_key.view_private_key(),
+            <&str>::try_from_ffi(self.report_url)?,
+            self.report_id.ffi_try_into()?,
+            self.authority_spki.as_slice(),
+        ))
+    }
+}
+
+/* ==== McAccountKeyHandle ==== */
+
+/// Most subaddresses, besides the default, change and gift code ones, whose
+/// keys an `AccountKeyHandle` keeps after deriving them.
+const RECENT_SUBADDRESS_CAPACITY: usize = 16;
+
+/// Keys of a single subaddress, derived once and then served from the cache of
+/// an `AccountKeyHandle`. The private keys are zeroized on drop, including
+/// those of copies handed out by the cache.
+#[derive(Clone)]
+pub struct SubaddressKeys {
+    pub view_private_key: RistrettoPrivate,
+    pub spend_private_key: RistrettoPrivate,
+    pub public_address: PublicAddress,
+}
+
+impl Drop for SubaddressKeys {
+    fn drop(&mut self) {
+        self.view_private_key.zeroize();
+        self.spend_private_key.zeroize();
+    }
+}
+
+impl SubaddressKeys {
+    fn derive(account_key: &AccountKey, subaddress_index: u64) -> Self {
+        Self {
+            view_private_key: account_key.subaddress_view_private(subaddress_index),
+            spend_private_key: account_key.subaddress_spend_private(subaddress_index),
+            public_address: account_key.subaddress(subaddress_index),
+        }
+    }
+}
+
+/// An `AccountKey` that is built once and kept on the Rust side, caching the
+/// subaddress keys derived from it. The default, change and gift code
+/// subaddresses are derived up front since nearly every transaction uses them.
+/// Keys of other subaddresses are kept for the `RECENT_SUBADDRESS_CAPACITY`
+/// most recently used ones, so that scanning many subaddresses does not grow
+/// the handle without bound.
+///
+/// Freeing the handle clears the account keys along with every cached
+/// subaddress key.
+pub struct AccountKeyHandle {
+    account_key: AccountKey,
+    reserved_subaddresses: ReservedSubaddresses,
+    reserved_subaddress_keys: BTreeMap<u64, SubaddressKeys>,
+    /// Most recently used first.
+    recent_subaddress_keys: Mutex<VecDeque<(u64, SubaddressKeys)>>,
+}
+
+impl AccountKeyHandle {
+    pub fn new(account_key: AccountKey) -> Self {
+        let reserved_subaddresses = ReservedSubaddresses::from(&account_key);
+        let reserved_subaddress_keys = [
+            DEFAULT_SUBADDRESS_INDEX,
+            CHANGE_SUBADDRESS_INDEX,
+            GIFT_CODE_SUBADDRESS_INDEX,
+        ]
+        .into_iter()
+        .map(|index| (index, SubaddressKeys::derive(&account_key, index)))
+        .collect();
+        Self {
+            account_key,
+            reserved_subaddresses,
+            reserved_subaddress_keys,
+            recent_subaddress_keys: Mutex::new(VecDeque::with_capacity(
+                RECENT_SUBADDRESS_CAPACITY + 1,
+            )),
+        }
+    }
+
+    pub fn account_key(&self) -> &AccountKey {
+        &self.account_key
+    }
+
+    pub fn reserved_subaddresses(&self) -> &ReservedSubaddresses {
+        &self.reserved_subaddresses
+    }
+
+    pub fn subaddress(&self, subaddress_index: u64) -> Result<SubaddressKeys, LibMcError> {
+        if let Some(subaddress) = self.reserved_subaddress_keys.get(&subaddress_index) {
+            return Ok(subaddress.clone());
+        }
+
+        let mut recent_subaddress_keys = self.recent_subaddress_keys.lock()?;
+        let entry = match recent_subaddress_keys
+            .iter()
+            .position(|(index, _)| *index == subaddress_index)
+        {
+            Some(position) => recent_subaddress_keys
+                .remove(position)
+                .expect("position is within bounds"),
+            None => (
+                subaddress_index,
+                SubaddressKeys::derive(&self.account_key, subaddress_index),
+            ),
+        };
+        let subaddress = entry.1.clone();
+        recent_subaddress_keys.push_front(entry);
+        // Evicted keys are zeroized as they are dropped.
+        recent_subaddress_keys.truncate(RECENT_SUBADDRESS_CAPACITY);
+        Ok(subaddress)
+    }
+}
+
+pub type McAccountKeyHandle = AccountKeyHandle;
+impl_into_ffi!(AccountKeyHandle);
+
+/// # Preconditions
+///
+/// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `spend_private_key` - must be a valid 32-byte Ristretto-format scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_create(
+    view_private_key: FfiRefPtr<McBuffer>,
+    spend_private_key: FfiRefPtr<McBuffer>,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McAccountKeyHandle> {
+    ffi_boundary_with_error(out_error, || {
+        let account_key = AccountKey::try_from_ffi(&McAccountKey {
+            view_private_key,
+            spend_private_key,
+            fog_info,
+        })?;
+        Ok(AccountKeyHandle::new(account_key))
+    })
+}
+
+/// # Preconditions
+///
+/// * `root_entropy` - must be 32 bytes in length.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_create_from_root_entropy(
+    root_entropy: FfiRefPtr<McBuffer>,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McAccountKeyHandle> {
+    ffi_boundary_with_error(out_error, || {
+        let root_entropy = <&[u8; 32]>::try_from_ffi(&root_entropy)?;
+        let mut account_key = AccountKey::from(&RootIdentity::from(root_entropy));
+        if let Some(fog_info) = fog_info.as_option() {
+            account_key = fog_info.add_to_account_key(&account_key)?;
+        }
+        Ok(AccountKeyHandle::new(account_key))
+    })
+}
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_create_from_mnemonic(
+    mnemonic: FfiStr,
+    account_index: u32,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McAccountKeyHandle> {
+    mc_account_key_handle_create_from_mnemonic_with_language(
+        mnemonic,
+        MC_BIP39_LANGUAGE_ENGLISH,
+        account_index,
+        fog_info,
+        out_error,
+    )
+}
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_create_from_mnemonic_with_language(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    account_index: u32,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McAccountKeyHandle> {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
+        let mut account_key = AccountKey::from(mnemonic.derive_slip10_key(account_index));
+        if let Some(fog_info) = fog_info.as_option() {
+            account_key = fog_info.add_to_account_key(&account_key)?;
+        }
+        Ok(AccountKeyHandle::new(account_key))
+    })
+}
+
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `spend_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_create_from_secrets(
+    view_private_key: FfiRefPtr<McSecret>,
+    spend_private_key: FfiRefPtr<McSecret>,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McAccountKeyHandle> {
+    ffi_boundary_with_error(out_error, || {
+        let view_private_key = ristretto_private_from_secret(&view_private_key)?;
+        let spend_private_key = ristretto_private_from_secret(&spend_private_key)?;
+
+        let mut account_key = AccountKey::new(&spend_private_key, &view_private_key);
+        if let Some(fog_info) = fog_info.as_option() {
+            account_key = fog_info.add_to_account_key(&account_key)?;
+        }
+        Ok(AccountKeyHandle::new(account_key))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_free(
+    account_key_handle: FfiOptOwnedPtr<McAccountKeyHandle>,
+) {
+    ffi_boundary(|| {
+        let _ = account_key_handle;
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_subaddress_view_private_key` - length must be >= 32.
+/// * `out_subaddress_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_get_subaddress_private_keys(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    subaddress_index: u64,
+    out_subaddress_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_subaddress_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress = account_key_handle.subaddress(subaddress_index)?;
+
+        out_subaddress_view_private_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPrivate::size())
+            .expect("out_subaddress_view_private_key length is insufficient")
+            .copy_from_slice(subaddress.view_private_key.as_ref());
+        out_subaddress_spend_private_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPrivate::size())
+            .expect("out_subaddress_spend_private_key length is insufficient")
+            .copy_from_slice(subaddress.spend_private_key.as_ref());
+        Ok(())
+    })
+}
+
+/// Like `mc_account_key_handle_get_subaddress_private_keys`, but the keys
+/// are returned as secrets rather than copied into foreign memory.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_get_subaddress_private_keys_to_secrets(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    subaddress_index: u64,
+    out_subaddress_view_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_subaddress_spend_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress = account_key_handle.subaddress(subaddress_index)?;
+
+        *out_subaddress_view_private_key.into_mut() =
+            private_key_to_secret(&subaddress.view_private_key);
+        *out_subaddress_spend_private_key.into_mut() =
+            private_key_to_secret(&subaddress.spend_private_key);
+        Ok(())
+    })
+}
+
+/// # Preconditions
+///
+/// * `out_subaddress_view_public_key` - length must be >= 32.
+/// * `out_subaddress_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_get_public_address_public_keys(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    subaddress_index: u64,
+    out_subaddress_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_subaddress_spend_public_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let public_address = account_key_handle
+            .subaddress(subaddress_index)?
+            .public_address
+            .clone();
+
+        out_subaddress_view_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_subaddress_view_public_key length is insufficient")
+            .copy_from_slice(&public_address.view_public_key().to_bytes());
+        out_subaddress_spend_public_key
+            .into_mut()
+            .as_slice_mut_of_len(RistrettoPublic::size())
+            .expect("out_subaddress_spend_public_key length is insufficient")
+            .copy_from_slice(&public_address.spend_public_key().to_bytes());
+        Ok(())
+    })
+}
+
+/* ==== Reserved Subaddresses ==== */
+
+#[repr(C)]
+pub struct McReservedSubaddressIndices {
+    default_subaddress_index: u64,
+    change_subaddress_index: u64,
+    gift_code_subaddress_index: u64,
+}
+
+pub type McReservedSubaddress = c_int;
+
+pub const MC_RESERVED_SUBADDRESS_DEFAULT: McReservedSubaddress = 0;
+pub const MC_RESERVED_SUBADDRESS_CHANGE: McReservedSubaddress = 1;
+pub const MC_RESERVED_SUBADDRESS_GIFT_CODE: McReservedSubaddress = 2;
+
+fn reserved_subaddress_index(reserved_subaddress: McReservedSubaddress) -> Option<u64> {
+    match reserved_subaddress {
+        MC_RESERVED_SUBADDRESS_DEFAULT => Some(DEFAULT_SUBADDRESS_INDEX),
+        MC_RESERVED_SUBADDRESS_CHANGE => Some(CHANGE_SUBADDRESS_INDEX),
+        MC_RESERVED_SUBADDRESS_GIFT_CODE => Some(GIFT_CODE_SUBADDRESS_INDEX),
+        _ => None,
+    }
+}
+
+/// Returns the subaddress indices that every account reserves for incoming
+/// payments, change and gift codes.
+#[no_mangle]
+pub extern "C" fn mc_account_key_get_reserved_subaddress_indices(
+    out_indices: FfiMutPtr<McReservedSubaddressIndices>,
+) -> bool {
+    ffi_boundary(|| {
+        *out_indices.into_mut() = McReservedSubaddressIndices {
+            default_subaddress_index: DEFAULT_SUBADDRESS_INDEX,
+            change_subaddress_index: CHANGE_SUBADDRESS_INDEX,
+            gift_code_subaddress_index: GIFT_CODE_SUBADDRESS_INDEX,
+        };
+    })
+}
+
+/// # Preconditions
+///
+/// * `account_key` - must be a valid account key.
+/// * `reserved_subaddress` - must be a valid `McReservedSubaddress`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+#[no_mangle]
+pub extern "C" fn mc_account_key_get_reserved_subaddress_keys(
+    account_key: FfiRefPtr<McAccountKey>,
+    reserved_subaddress: McReservedSubaddress,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+) -> bool {
+    ffi_boundary(|| {
+        let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
+        let subaddress_index =
+            reserved_subaddress_index(reserved_subaddress).expect("reserved_subaddress is invalid");
+
+        copy_subaddress_keys(
+            &SubaddressKeys::derive(&account_key, subaddress_index),
+            out_view_private_key,
+            out_spend_private_key,
+            out_view_public_key,
+            out_spend_public_key,
+        );
+    })
+}
+
+/// # Preconditions
+///
+/// * `reserved_subaddress` - must be a valid `McReservedSubaddress`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+/// * `out_view_public_key` - length must be >= 32.
+/// * `out_spend_public_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+#[no_mangle]
+pub extern "C" fn mc_account_key_handle_get_reserved_subaddress_keys(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    reserved_subaddress: McReservedSubaddress,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress_index =
+            reserved_subaddress_index(reserved_subaddress).expect("reserved_subaddress is invalid");
+
+        copy_subaddress_keys(
+            &account_key_handle.subaddress(subaddress_index)?,
+            out_view_private_key,
+            out_spend_private_key,
+            out_view_public_key,
+            out_spend_public_key,
+        );
+        Ok(())
+    })
+}
+
+fn copy_subaddress_keys(
+    subaddress: &SubaddressKeys,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_view_public_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_public_key: FfiMutPtr<McMutableBuffer>,
+) {
+    out_view_private_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPrivate::size())
+        .expect("out_view_private_key length is insufficient")
+        .copy_from_slice(subaddress.view_private_key.as_ref());
+    out_spend_private_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPrivate::size())
+        .expect("out_spend_private_key length is insufficient")
+        .copy_from_slice(subaddress.spend_private_key.as_ref());
+    out_view_public_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPublic::size())
+        .expect("out_view_public_key length is insufficient")
+        .copy_from_slice(&subaddress.public_address.view_public_key().to_bytes());
+    out_spend_public_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPublic::size())
+        .expect("out_spend_public_key length is insufficient")
+        .copy_from_slice(&subaddress.public_address.spend_public_key().to_bytes());
+}
+
+/* ==== Account Key Derivations ==== */
+
 /// # Preconditions
 ///
 /// * `root_entropy` - must be 32 bytes in length.
@@ -73,6 +586,33 @@ pub extern "C" fn mc_account_private_keys_from_root_entropy(
     })
 }
 
+/// Like `mc_account_private_keys_from_root_entropy`, but both the entropy and
+/// the derived keys stay in Rust-owned memory.
+///
+/// # Preconditions
+///
+/// * `root_entropy` - must hold 32 bytes.
+#[no_mangle]
+pub extern "C" fn mc_account_private_keys_from_root_entropy_to_secrets(
+    root_entropy: FfiRefPtr<McSecret>,
+    out_view_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_spend_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+) -> bool {
+    ffi_boundary(|| {
+        let root_entropy = <&[u8; 32]>::try_from(root_entropy.as_slice())
+            .expect("root_entropy must be 32 bytes in length");
+
+        let account_key = AccountKey::from(&RootIdentity::from(root_entropy));
+        *out_view_private_key.into_mut() = private_key_to_secret(account_key.view_private_key());
+        *out_spend_private_key.into_mut() = private_key_to_secret(account_key.spend_private_key());
+    })
+}
+
+pub(crate) fn private_key_to_secret(private_key: &RistrettoPrivate) -> FfiOptOwnedPtr<McSecret> {
+    let bytes: &[u8] = private_key.as_ref();
+    FfiOwnedPtr::new(Zeroizing::new(bytes.to_vec())).into()
+}
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -165,20 +705,52 @@ pub extern "C" fn mc_account_key_get_public_address_fog_authority_sig(
     ffi_boundary(|| {
         let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
 
-        let public_address = account_key.subaddress(subaddress_index);
-        let fog_authority_sig = public_address
-            .fog_authority_sig()
-            .expect("account_key does not contain fog info");
+        copy_fog_authority_sig(
+            &account_key.subaddress(subaddress_index),
+            out_fog_authority_sig,
+        );
+    })
+}
 
-        let out_fog_authority_sig = out_fog_authority_sig
-            .into_mut()
-            .as_slice_mut_of_len(fog_authority_sig.len())
-            .expect("out_fog_authority_sig length is insufficient");
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+/// * `out_fog_authority_sig` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::Poison`
+#[no_mangle]
+pub extern "C" fn mc_account_key_get_public_address_fog_authority_sig_with_handle(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    subaddress_index: u64,
+    out_fog_authority_sig: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let subaddress = account_key_handle.subaddress(subaddress_index)?;
 
-        out_fog_authority_sig.copy_from_slice(fog_authority_sig);
+        copy_fog_authority_sig(&subaddress.public_address, out_fog_authority_sig);
+        Ok(())
     })
 }
 
+fn copy_fog_authority_sig(
+    public_address: &PublicAddress,
+    out_fog_authority_sig: FfiMutPtr<McMutableBuffer>,
+) {
+    let fog_authority_sig = public_address
+        .fog_authority_sig()
+        .expect("account_key does not contain fog info");
+
+    let out_fog_authority_sig = out_fog_authority_sig
+        .into_mut()
+        .as_slice_mut_of_len(fog_authority_sig.len())
+        .expect("out_fog_authority_sig length is insufficient");
+
+    out_fog_authority_sig.copy_from_slice(fog_authority_sig);
+}
+
 /* ==== TxOutMemoBuilder ==== */
 
 /// # Preconditions
@@ -252,3 +824,328 @@ impl<'a> TryFromFfi<&McPublicAddress<'a>> for PublicAddress {
         }
     }
 }
+
+/* ==== Fog Authority Signature Verification ==== */
+
+pub type McFogAuthoritySigStatus = c_int;
+
+pub const MC_FOG_AUTHORITY_SIG_STATUS_VALID: McFogAuthoritySigStatus = 0;
+pub const MC_FOG_AUTHORITY_SIG_STATUS_NO_FOG_INFO: McFogAuthoritySigStatus = 1;
+pub const MC_FOG_AUTHORITY_SIG_STATUS_REPORT_URL_MISMATCH: McFogAuthoritySigStatus = 2;
+pub const MC_FOG_AUTHORITY_SIG_STATUS_REPORT_ID_MISMATCH: McFogAuthoritySigStatus = 3;
+pub const MC_FOG_AUTHORITY_SIG_STATUS_MALFORMED_SIGNATURE: McFogAuthoritySigStatus = 4;
+pub const MC_FOG_AUTHORITY_SIG_STATUS_SIGNATURE_MISMATCH: McFogAuthoritySigStatus = 5;
+
+fn fog_authority_sig_status(
+    public_address: &PublicAddress,
+    fog_authority_spki: &[u8],
+    expected_report_url: Option<&FogUri>,
+    expected_report_id: Option<&str>,
+) -> McFogAuthoritySigStatus {
+    let (report_url, report_id, authority_sig) = match (
+        public_address.fog_report_url(),
+        public_address.fog_report_id(),
+        public_address.fog_authority_sig(),
+    ) {
+        (Some(report_url), Some(report_id), Some(authority_sig)) => {
+            (report_url, report_id, authority_sig)
+        }
+        _ => return MC_FOG_AUTHORITY_SIG_STATUS_NO_FOG_INFO,
+    };
+
+    // Report URLs are compared in their normalized `FogUri` form, as the fog
+    // resolver keys report responses by it.
+    if let Some(expected_report_url) = expected_report_url {
+        let matches = FogUri::from_str(report_url).map_or(false, |report_url| {
+            report_url.to_string() == expected_report_url.to_string()
+        });
+        if !matches {
+            return MC_FOG_AUTHORITY_SIG_STATUS_REPORT_URL_MISMATCH;
+        }
+    }
+    if expected_report_id.map_or(false, |expected| expected != report_id) {
+        return MC_FOG_AUTHORITY_SIG_STATUS_REPORT_ID_MISMATCH;
+    }
+    let authority_sig = match RistrettoSignature::try_from(authority_sig) {
+        Ok(authority_sig) => authority_sig,
+        Err(_) => return MC_FOG_AUTHORITY_SIG_STATUS_MALFORMED_SIGNATURE,
+    };
+    // The authority signature is made with the subaddress view private key.
+    match public_address
+        .view_public_key()
+        .verify_authority(fog_authority_spki, &authority_sig)
+    {
+        Ok(()) => MC_FOG_AUTHORITY_SIG_STATUS_VALID,
+        Err(_) => MC_FOG_AUTHORITY_SIG_STATUS_SIGNATURE_MISMATCH,
+    }
+}
+
+/// Checks that `public_address` was issued by the fog operator with
+/// `fog_authority_spki`, and optionally that it uses the expected report
+/// server. Report URLs are compared in their normalized `FogUri` form. A
+/// mismatch is not an error: the reason is reported through `out_status`.
+///
+/// # Preconditions
+///
+/// * `public_address` - must be a valid `PublicAddress`.
+/// * `expected_report_url` - must be null or else a nul-terminated C string
+///   containing valid UTF-8.
+/// * `expected_report_id` - must be null or else a nul-terminated C string
+///   containing valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `expected_report_url` is not a valid Fog
+///   report uri.
+#[no_mangle]
+pub extern "C" fn mc_public_address_verify_fog_authority_sig(
+    public_address: FfiRefPtr<McPublicAddress>,
+    fog_authority_spki: FfiRefPtr<McBuffer>,
+    expected_report_url: FfiOptStr,
+    expected_report_id: FfiOptStr,
+    out_status: FfiMutPtr<McFogAuthoritySigStatus>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let public_address = PublicAddress::try_from_ffi(&public_address)?;
+        let expected_report_url = Option::<&str>::try_from_ffi(expected_report_url)
+            .expect("expected_report_url is invalid")
+            .map(FogUri::from_str)
+            .transpose()
+            .map_err(|err| LibMcError::InvalidInput(err.to_string()))?;
+        let expected_report_id = Option::<&str>::try_from_ffi(expected_report_id)
+            .expect("expected_report_id is invalid");
+
+        *out_status.into_mut() = fog_authority_sig_status(
+            &public_address,
+            fog_authority_spki.as_slice(),
+            expected_report_url.as_ref(),
+            expected_report_id,
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn account_key_handle() -> AccountKeyHandle {
+        AccountKeyHandle::new(AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0)))
+    }
+
+    fn recent_subaddress_indices(account_key_handle: &AccountKeyHandle) -> Vec<u64> {
+        account_key_handle
+            .recent_subaddress_keys
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    #[test]
+    fn subaddress_keys_match_account_key() {
+        let account_key_handle = account_key_handle();
+        let account_key = account_key_handle.account_key();
+
+        for subaddress_index in [
+            DEFAULT_SUBADDRESS_INDEX,
+            CHANGE_SUBADDRESS_INDEX,
+            GIFT_CODE_SUBADDRESS_INDEX,
+            1,
+            1000,
+        ] {
+            // Ask twice, to compare both freshly derived and cached keys.
+            for _ in 0..2 {
+                let subaddress = account_key_handle.subaddress(subaddress_index).unwrap();
+                assert_eq!(
+                    subaddress.view_private_key,
+                    account_key.subaddress_view_private(subaddress_index)
+                );
+                assert_eq!(
+                    subaddress.spend_private_key,
+                    account_key.subaddress_spend_private(subaddress_index)
+                );
+                assert_eq!(
+                    subaddress.public_address,
+                    account_key.subaddress(subaddress_index)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reserved_subaddresses_are_not_cached_as_recent() {
+        let account_key_handle = account_key_handle();
+
+        account_key_handle
+            .subaddress(DEFAULT_SUBADDRESS_INDEX)
+            .unwrap();
+        account_key_handle
+            .subaddress(CHANGE_SUBADDRESS_INDEX)
+            .unwrap();
+        assert!(recent_subaddress_indices(&account_key_handle).is_empty());
+    }
+
+    #[test]
+    fn recent_subaddress_cache_is_bounded() {
+        let account_key_handle = account_key_handle();
+        let capacity = RECENT_SUBADDRESS_CAPACITY as u64;
+
+        for subaddress_index in 1..=2 * capacity {
+            account_key_handle.subaddress(subaddress_index).unwrap();
+        }
+        let expected: Vec<u64> = (capacity + 1..=2 * capacity).rev().collect();
+        assert_eq!(recent_subaddress_indices(&account_key_handle), expected);
+    }
+
+    #[test]
+    fn least_recently_used_subaddress_is_evicted() {
+        let account_key_handle = account_key_handle();
+        let capacity = RECENT_SUBADDRESS_CAPACITY as u64;
+
+        for subaddress_index in 1..=capacity {
+            account_key_handle.subaddress(subaddress_index).unwrap();
+        }
+        // Using subaddress 1 again makes subaddress 2 the least recently used.
+        account_key_handle.subaddress(1).unwrap();
+        account_key_handle.subaddress(capacity + 1).unwrap();
+
+        let recent = recent_subaddress_indices(&account_key_handle);
+        assert_eq!(recent.len(), RECENT_SUBADDRESS_CAPACITY);
+        assert_eq!(&recent[..2], &[capacity + 1, 1]);
+        assert!(!recent.contains(&2));
+    }
+
+    #[test]
+    fn reserved_subaddresses_match_account_key() {
+        let account_key = AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0));
+        let reserved_subaddresses = [
+            (
+                MC_RESERVED_SUBADDRESS_DEFAULT,
+                account_key.default_subaddress(),
+            ),
+            (
+                MC_RESERVED_SUBADDRESS_CHANGE,
+                account_key.change_subaddress(),
+            ),
+            (
+                MC_RESERVED_SUBADDRESS_GIFT_CODE,
+                account_key.gift_code_subaddress(),
+            ),
+        ];
+
+        for (reserved_subaddress, expected) in reserved_subaddresses {
+            let subaddress_index = reserved_subaddress_index(reserved_subaddress).unwrap();
+            let keys = SubaddressKeys::derive(&account_key, subaddress_index);
+            assert_eq!(keys.public_address, expected);
+            assert_eq!(
+                RistrettoPublic::from(&keys.view_private_key),
+                *expected.view_public_key()
+            );
+            assert_eq!(
+                RistrettoPublic::from(&keys.spend_private_key),
+                *expected.spend_public_key()
+            );
+        }
+        assert_eq!(reserved_subaddress_index(3), None);
+    }
+
+    const FOG_AUTHORITY_SPKI: &[u8] = b"fog authority spki";
+
+    fn fog_public_address() -> PublicAddress {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        AccountKey::new_with_fog(
+            &RistrettoPrivate::from_random(&mut rng),
+            &RistrettoPrivate::from_random(&mut rng),
+            "fog://fog.example.com",
+            "1".to_owned(),
+            FOG_AUTHORITY_SPKI,
+        )
+        .default_subaddress()
+    }
+
+    fn fog_uri(report_url: &str) -> FogUri {
+        FogUri::from_str(report_url).unwrap()
+    }
+
+    #[test]
+    fn valid_fog_authority_sig_matches_normalized_report_url() {
+        let public_address = fog_public_address();
+        for expected_report_url in ["fog://fog.example.com", "fog://fog.example.com:443/"] {
+            assert_eq!(
+                fog_authority_sig_status(
+                    &public_address,
+                    FOG_AUTHORITY_SPKI,
+                    Some(&fog_uri(expected_report_url)),
+                    Some("1"),
+                ),
+                MC_FOG_AUTHORITY_SIG_STATUS_VALID
+            );
+        }
+        assert_eq!(
+            fog_authority_sig_status(&public_address, FOG_AUTHORITY_SPKI, None, None),
+            MC_FOG_AUTHORITY_SIG_STATUS_VALID
+        );
+    }
+
+    #[test]
+    fn public_address_without_fog_has_no_fog_info() {
+        let public_address =
+            AccountKey::random(&mut ChaCha20Rng::seed_from_u64(0)).default_subaddress();
+        assert_eq!(
+            fog_authority_sig_status(&public_address, FOG_AUTHORITY_SPKI, None, None),
+            MC_FOG_AUTHORITY_SIG_STATUS_NO_FOG_INFO
+        );
+    }
+
+    #[test]
+    fn mismatched_report_url_and_id_are_reported() {
+        let public_address = fog_public_address();
+        assert_eq!(
+            fog_authority_sig_status(
+                &public_address,
+                FOG_AUTHORITY_SPKI,
+                Some(&fog_uri("fog://other.example.com")),
+                Some("1"),
+            ),
+            MC_FOG_AUTHORITY_SIG_STATUS_REPORT_URL_MISMATCH
+        );
+        assert_eq!(
+            fog_authority_sig_status(
+                &public_address,
+                FOG_AUTHORITY_SPKI,
+                Some(&fog_uri("fog://fog.example.com")),
+                Some("2"),
+            ),
+            MC_FOG_AUTHORITY_SIG_STATUS_REPORT_ID_MISMATCH
+        );
+    }
+
+    #[test]
+    fn malformed_fog_authority_sig_is_reported() {
+        let public_address = fog_public_address();
+        let public_address = PublicAddress::new_with_fog(
+            public_address.spend_public_key(),
+            public_address.view_public_key(),
+            "fog://fog.example.com",
+            "1".to_owned(),
+            vec![1, 2, 3],
+        );
+        assert_eq!(
+            fog_authority_sig_status(&public_address, FOG_AUTHORITY_SPKI, None, None),
+            MC_FOG_AUTHORITY_SIG_STATUS_MALFORMED_SIGNATURE
+        );
+    }
+
+    #[test]
+    fn fog_authority_sig_over_other_spki_is_a_mismatch() {
+        assert_eq!(
+            fog_authority_sig_status(&fog_public_address(), b"other spki", None, None),
+            MC_FOG_AUTHORITY_SIG_STATUS_SIGNATURE_MISMATCH
+        );
+    }
+}
@@ -4,13 +4,18 @@
 pub mod common;
 
 pub mod attest;
+pub mod backup;
 pub mod bip39;
 pub mod chacha20_rng;
 pub mod crypto;
 pub mod encodings;
+#[cfg(feature = "fake-fog-ingest")]
+pub mod fake_fog_ingest;
 pub mod fog;
+pub mod gift_code;
 pub mod keys;
 pub mod slip10;
+pub mod slip39;
 pub mod transaction;
 
 mod error;
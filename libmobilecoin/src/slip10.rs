@@ -1,11 +1,23 @@
 // Copyright (c) 2018-2022 The MobileCoin Foundation
 
-use crate::{common::*, LibMcError};
-use bip39::{Language, Mnemonic};
+use crate::{
+    bip39::{McBip39Language, MC_BIP39_LANGUAGE_ENGLISH},
+    common::*,
+    keys::{private_key_to_secret, AccountKeyHandle, McAccountKeyFogInfo, McAccountKeyHandle},
+    LibMcError,
+};
+use bip39::{Language, Mnemonic, Seed};
+use core::ffi::c_void;
 use mc_account_keys::AccountKey;
-use mc_account_keys_slip10::Slip10KeyGenerator;
+use mc_account_keys_slip10::{Slip10Key, Slip10KeyGenerator};
 use mc_crypto_keys::{ReprBytes, RistrettoPrivate};
 use mc_util_ffi::*;
+use zeroize::Zeroizing;
+
+/// BIP-0044 purpose, as used by `Slip10KeyGenerator`.
+const BIP44_PURPOSE: u32 = 44;
+/// SLIP-0044 registered coin type for MobileCoin.
+const MOBILECOIN_COIN_TYPE: u32 = 866;
 
 /// # Preconditions
 ///
@@ -23,25 +35,469 @@ pub extern "C" fn mc_slip10_account_private_keys_from_mnemonic(
     out_view_private_key: FfiMutPtr<McMutableBuffer>,
     out_spend_private_key: FfiMutPtr<McMutableBuffer>,
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    mc_slip10_account_private_keys_from_mnemonic_with_language(
+        mnemonic,
+        MC_BIP39_LANGUAGE_ENGLISH,
+        account_index,
+        out_view_private_key,
+        out_spend_private_key,
+        out_error,
+    )
+}
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_slip10_account_private_keys_from_mnemonic_with_language(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    account_index: u32,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> bool {
     ffi_boundary_with_error(out_error, || {
         let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
 
-        let mnemonic = Mnemonic::from_phrase(mnemonic, Language::English)
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
             .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
-        let key = mnemonic.derive_slip10_key(account_index);
-        let account_key = AccountKey::from(key);
+        let account_key = AccountKey::from(slip10_key_from_mnemonic(mnemonic, "", account_index));
+
+        copy_account_private_keys(&account_key, out_view_private_key, out_spend_private_key);
+        Ok(())
+    })
+}
+
+/// Derives account keys from a mnemonic and a BIP39 passphrase (the "25th
+/// word"). A null or empty `passphrase` derives the same keys as
+/// `mc_slip10_account_private_keys_from_mnemonic_with_language`.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+/// * `out_view_private_key` - length must be >= 32.
+/// * `out_spend_private_key` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_slip10_account_private_keys_from_mnemonic_with_passphrase(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    passphrase: FfiOptStr,
+    account_index: u32,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+        let passphrase = Option::<&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
+        let account_key = AccountKey::from(slip10_key_from_mnemonic(
+            mnemonic,
+            passphrase.unwrap_or(""),
+            account_index,
+        ));
+
+        copy_account_private_keys(&account_key, out_view_private_key, out_spend_private_key);
+        Ok(())
+    })
+}
+
+/// Like `mc_slip10_account_private_keys_from_mnemonic_with_passphrase`, but
+/// the keys are returned as secrets rather than copied into foreign memory.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_slip10_account_private_keys_from_mnemonic_to_secrets(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    passphrase: FfiOptStr,
+    account_index: u32,
+    out_view_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_spend_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+        let passphrase = Option::<&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
+        let account_key = AccountKey::from(slip10_key_from_mnemonic(
+            mnemonic,
+            passphrase.unwrap_or(""),
+            account_index,
+        ));
+
+        *out_view_private_key.into_mut() = private_key_to_secret(account_key.view_private_key());
+        *out_spend_private_key.into_mut() = private_key_to_secret(account_key.spend_private_key());
+        Ok(())
+    })
+}
+
+/// Derives account keys using `master_secret` directly as the SLIP-0010 seed,
+/// as SLIP-0039 prescribes for a master secret recovered with
+/// `mc_slip39_combine_shares`.
+///
+/// Note that this is not the derivation used for a mnemonic: if the shares
+/// hold BIP39 entropy, convert the recovered secret back into a mnemonic
+/// instead.
+///
+/// # Preconditions
+///
+/// * `master_secret` - must hold between 16 and 64 bytes.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_slip10_account_private_keys_from_master_secret(
+    master_secret: FfiRefPtr<McSecret>,
+    account_index: u32,
+    out_view_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_spend_private_key: FfiMutPtr<FfiOptOwnedPtr<McSecret>>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        if !(16..=64).contains(&master_secret.len()) {
+            return Err(LibMcError::InvalidInput(format!(
+                "master_secret must be between 16 and 64 bytes, got {}",
+                master_secret.len()
+            )));
+        }
+        let account_key = AccountKey::from(slip10_key_from_seed(&master_secret, account_index));
 
-        out_view_private_key
-            .into_mut()
-            .as_slice_mut_of_len(RistrettoPrivate::size())
-            .expect("out_view_private_key length is insufficient")
-            .copy_from_slice(account_key.view_private_key().as_ref());
-        out_spend_private_key
-            .into_mut()
-            .as_slice_mut_of_len(RistrettoPrivate::size())
-            .expect("out_spend_private_key length is insufficient")
-            .copy_from_slice(account_key.spend_private_key().as_ref());
+        *out_view_private_key.into_mut() = private_key_to_secret(account_key.view_private_key());
+        *out_spend_private_key.into_mut() = private_key_to_secret(account_key.spend_private_key());
         Ok(())
     })
 }
+
+/* ==== McSlip10AccountDiscovery ==== */
+
+/// Callback used by `mc_slip10_discover_accounts` to ask foreign code whether
+/// the account at `account_index` has ever been used.
+#[repr(C)]
+pub struct McSlip10AccountActivityCallback<'a> {
+    pub has_activity: FfiAccountActivityCallback,
+    pub context: FfiOptMutPtr<'a, c_void>,
+}
+
+/// Transparent wrapper around a function pointer with the C signature `bool
+/// (*)(void*, uint32_t, const McAccountKeyHandle*)`.
+///
+/// `null` is not considered a valid value.
+#[repr(transparent)]
+pub struct FfiAccountActivityCallback(
+    unsafe extern "C" fn(*mut c_void, u32, *const McAccountKeyHandle) -> bool,
+);
+
+impl<'a> McSlip10AccountActivityCallback<'a> {
+    fn has_activity(&mut self, account_index: u32, account_key: &AccountKeyHandle) -> bool {
+        unsafe { (self.has_activity.0)(self.context.as_raw(), account_index, account_key) }
+    }
+}
+
+/// Indices of the accounts found by `mc_slip10_discover_accounts`, in
+/// ascending order.
+pub type McSlip10DiscoveredAccounts = Vec<u32>;
+impl_into_ffi!(Vec<u32>);
+
+/// Derives accounts `0..max_account_count` from `mnemonic` in order, calling
+/// `callback` for each, and stops once `gap_limit` consecutive accounts have
+/// reported no activity.
+///
+/// The `McAccountKeyHandle` passed to `callback` is only valid for the
+/// duration of the call.
+///
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+/// * `language` - must be a valid `McBip39Language`.
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+/// * `gap_limit` - must be greater than 0.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_slip10_discover_accounts(
+    mnemonic: FfiStr,
+    language: McBip39Language,
+    passphrase: FfiOptStr,
+    fog_info: FfiOptRefPtr<McAccountKeyFogInfo>,
+    gap_limit: u32,
+    max_account_count: u32,
+    callback: FfiMutPtr<McSlip10AccountActivityCallback>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McSlip10DiscoveredAccounts> {
+    ffi_boundary_with_error(out_error, || {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        let language = Language::try_from_ffi(language).expect("language is invalid");
+        let passphrase = Option::<&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+        assert!(gap_limit > 0, "gap_limit must be greater than 0");
+        let callback = callback.into_mut();
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic, language)
+            .map_err(|err| LibMcError::InvalidInput(format!("Invalid mnemonic: {}", err)))?;
+
+        discover_accounts(
+            &mnemonic,
+            passphrase.unwrap_or(""),
+            fog_info.as_option(),
+            gap_limit,
+            max_account_count,
+            |account_index, account_key| callback.has_activity(account_index, account_key),
+        )
+    })
+}
+
+fn discover_accounts(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    fog_info: Option<&McAccountKeyFogInfo>,
+    gap_limit: u32,
+    max_account_count: u32,
+    mut has_activity: impl FnMut(u32, &AccountKeyHandle) -> bool,
+) -> Result<McSlip10DiscoveredAccounts, LibMcError> {
+    let mut used_accounts = McSlip10DiscoveredAccounts::new();
+    let mut unused_in_a_row = 0;
+    // Stretching the mnemonic into a seed is the expensive step, so it is done
+    // once rather than for every account index.
+    let seed = Seed::new(mnemonic, passphrase);
+    for account_index in 0..max_account_count {
+        let mut account_key =
+            AccountKey::from(slip10_key_from_seed(seed.as_bytes(), account_index));
+        if let Some(fog_info) = fog_info {
+            account_key = fog_info.add_to_account_key(&account_key)?;
+        }
+        let account_key = AccountKeyHandle::new(account_key);
+
+        if has_activity(account_index, &account_key) {
+            used_accounts.push(account_index);
+            unused_in_a_row = 0;
+        } else {
+            unused_in_a_row += 1;
+            if unused_in_a_row >= gap_limit {
+                break;
+            }
+        }
+    }
+    Ok(used_accounts)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_slip10_discovered_accounts_free(
+    discovered_accounts: FfiOptOwnedPtr<McSlip10DiscoveredAccounts>,
+) {
+    ffi_boundary(|| {
+        let _ = discovered_accounts;
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_slip10_discovered_accounts_get_count(
+    discovered_accounts: FfiRefPtr<McSlip10DiscoveredAccounts>,
+) -> usize {
+    ffi_boundary(|| discovered_accounts.len())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the discovered account count.
+#[no_mangle]
+pub extern "C" fn mc_slip10_discovered_accounts_get_account_index(
+    discovered_accounts: FfiRefPtr<McSlip10DiscoveredAccounts>,
+    index: usize,
+) -> u32 {
+    ffi_boundary(|| *discovered_accounts.get(index).expect("index out of bounds"))
+}
+
+/// Derives the SLIP-0010 key at `m/44'/866'/account_index'` from the BIP39
+/// seed of `mnemonic` salted with `passphrase`.
+pub(crate) fn slip10_key_from_mnemonic(
+    mnemonic: Mnemonic,
+    passphrase: &str,
+    account_index: u32,
+) -> Slip10Key {
+    if passphrase.is_empty() {
+        return mnemonic.derive_slip10_key(account_index);
+    }
+    let seed = Seed::new(&mnemonic, passphrase);
+    slip10_key_from_seed(seed.as_bytes(), account_index)
+}
+
+/// Derives the SLIP-0010 key at `m/44'/866'/account_index'` from `seed`.
+fn slip10_key_from_seed(seed: &[u8], account_index: u32) -> Slip10Key {
+    let path = [BIP44_PURPOSE, MOBILECOIN_COIN_TYPE, account_index];
+    let key = Zeroizing::new(slip10_ed25519::derive_ed25519_private_key(seed, &path));
+    Slip10Key::from(*key)
+}
+
+fn copy_account_private_keys(
+    account_key: &AccountKey,
+    out_view_private_key: FfiMutPtr<McMutableBuffer>,
+    out_spend_private_key: FfiMutPtr<McMutableBuffer>,
+) {
+    out_view_private_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPrivate::size())
+        .expect("out_view_private_key length is insufficient")
+        .copy_from_slice(account_key.view_private_key().as_ref());
+    out_spend_private_key
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPrivate::size())
+        .expect("out_spend_private_key length is insufficient")
+        .copy_from_slice(account_key.spend_private_key().as_ref());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                            abandon abandon abandon about";
+
+    fn private_key_bytes(key: Slip10Key) -> (Vec<u8>, Vec<u8>) {
+        let account_key = AccountKey::from(key);
+        (
+            account_key.view_private_key().to_bytes().to_vec(),
+            account_key.spend_private_key().to_bytes().to_vec(),
+        )
+    }
+
+    #[test]
+    fn empty_passphrase_matches_derivation_without_passphrase() {
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        for account_index in 0..3 {
+            let expected = private_key_bytes(mnemonic.clone().derive_slip10_key(account_index));
+
+            let seed = Seed::new(&mnemonic, "");
+            let from_seed = private_key_bytes(slip10_key_from_seed(seed.as_bytes(), account_index));
+            assert_eq!(from_seed, expected);
+
+            let with_passphrase = private_key_bytes(slip10_key_from_mnemonic(
+                mnemonic.clone(),
+                "",
+                account_index,
+            ));
+            assert_eq!(with_passphrase, expected);
+        }
+    }
+
+    #[test]
+    fn passphrase_salts_bip39_seed() {
+        // BIP39 reference vector for all-zero 128-bit entropy with passphrase
+        // "TREZOR".
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        let seed = Seed::new(&mnemonic, "TREZOR");
+        assert_eq!(
+            hex::encode(seed.as_bytes()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        let with_passphrase =
+            private_key_bytes(slip10_key_from_mnemonic(mnemonic.clone(), "TREZOR", 0));
+        assert_eq!(
+            with_passphrase,
+            private_key_bytes(slip10_key_from_seed(seed.as_bytes(), 0))
+        );
+        assert_ne!(
+            with_passphrase,
+            private_key_bytes(slip10_key_from_mnemonic(mnemonic, "", 0))
+        );
+    }
+
+    #[test]
+    fn slip10_ed25519_reference_vector() {
+        // SLIP-0010 test vector 1 for ed25519, chain m/0H.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            hex::encode(slip10_ed25519::derive_ed25519_private_key(&seed, &[0])),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    fn discover(used: &[u32], gap_limit: u32, max_account_count: u32) -> (Vec<u32>, Vec<u32>) {
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        let mut checked = Vec::new();
+        let discovered = discover_accounts(
+            &mnemonic,
+            "",
+            None,
+            gap_limit,
+            max_account_count,
+            |account_index, _| {
+                checked.push(account_index);
+                used.contains(&account_index)
+            },
+        )
+        .unwrap();
+        (discovered, checked)
+    }
+
+    #[test]
+    fn discovery_continues_across_gaps_shorter_than_the_limit() {
+        let (discovered, checked) = discover(&[0, 2, 5], 3, 100);
+        assert_eq!(discovered, [0, 2, 5]);
+        assert_eq!(checked, (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn discovery_stops_at_the_gap_limit() {
+        let (discovered, checked) = discover(&[0, 2, 5], 2, 100);
+        assert_eq!(discovered, [0, 2]);
+        assert_eq!(checked, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn discovery_stops_at_the_max_account_count() {
+        let (discovered, checked) = discover(&[0, 1, 2, 3], 2, 3);
+        assert_eq!(discovered, [0, 1, 2]);
+        assert_eq!(checked, [0, 1, 2]);
+    }
+
+    #[test]
+    fn discovered_accounts_are_derived_from_the_mnemonic() {
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        discover_accounts(&mnemonic, "", None, 1, 2, |account_index, account_key| {
+            let expected = AccountKey::from(mnemonic.clone().derive_slip10_key(account_index));
+            assert_eq!(
+                account_key.account_key().default_subaddress(),
+                expected.default_subaddress()
+            );
+            true
+        })
+        .unwrap();
+    }
+}
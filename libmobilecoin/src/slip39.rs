@@ -0,0 +1,234 @@
+// Copyright (c) 2018-2022 The MobileCoin Foundation
+
+//! SLIP-0039 Shamir secret sharing of wallet entropy.
+//!
+//! The master secret is split into a single group of `threshold`-of-`count`
+//! member shares, each encoded as a mnemonic from the SLIP-0039 wordlist.
+
+use crate::{common::*, LibMcError};
+use mc_util_ffi::*;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The PBKDF2 iteration exponent used when encrypting the master secret, i.e.
+/// 10000 * 2^1 rounds, matching the reference implementation's default.
+const ITERATION_EXPONENT: u8 = 1;
+
+/* ==== McSlip39Shares ==== */
+
+pub type McSlip39Shares = Vec<Zeroizing<String>>;
+impl_into_ffi!(Vec<Zeroizing<String>>);
+
+#[no_mangle]
+pub extern "C" fn mc_slip39_shares_create() -> FfiOptOwnedPtr<McSlip39Shares> {
+    ffi_boundary(Vec::new)
+}
+
+#[no_mangle]
+pub extern "C" fn mc_slip39_shares_free(shares: FfiOptOwnedPtr<McSlip39Shares>) {
+    ffi_boundary(|| {
+        let _ = shares;
+    })
+}
+
+/// # Preconditions
+///
+/// * `mnemonic` - must be a nul-terminated C string containing valid UTF-8.
+#[no_mangle]
+pub extern "C" fn mc_slip39_shares_add(
+    shares: FfiMutPtr<McSlip39Shares>,
+    mnemonic: FfiStr,
+) -> bool {
+    ffi_boundary(|| {
+        let mnemonic = <&str>::try_from_ffi(mnemonic).expect("mnemonic is invalid");
+        shares.into_mut().push(Zeroizing::new(mnemonic.to_owned()));
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mc_slip39_shares_get_count(shares: FfiRefPtr<McSlip39Shares>) -> usize {
+    ffi_boundary(|| shares.len())
+}
+
+/// # Preconditions
+///
+/// * `index` - must be less than the share count.
+#[no_mangle]
+pub extern "C" fn mc_slip39_shares_get_share(
+    shares: FfiRefPtr<McSlip39Shares>,
+    index: usize,
+) -> FfiOptOwnedStr {
+    ffi_boundary(|| {
+        let share = shares.get(index).expect("index out of bounds");
+        FfiOwnedStr::ffi_try_from(share.as_str())
+            .expect("share could not be converted to a C string")
+    })
+}
+
+/* ==== McSlip39 ==== */
+
+/// Splits `master_secret` into `share_count` mnemonic shares, any `threshold`
+/// of which recover it. `passphrase` encrypts the master secret and must be
+/// supplied again to `mc_slip39_combine_shares`. A null passphrase is treated
+/// as empty.
+///
+/// The share values and split identifier are drawn from the operating
+/// system's CSPRNG rather than an `McRngCallback`: `sssmc39` draws them from
+/// `rand::thread_rng()` internally and does not accept a caller-supplied RNG.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - `master_secret` is shorter than 16 bytes or
+///   has an odd length, or `threshold` is 0 or greater than `share_count`.
+#[no_mangle]
+pub extern "C" fn mc_slip39_split_secret(
+    master_secret: FfiRefPtr<McSecret>,
+    passphrase: FfiOptStr,
+    threshold: u8,
+    share_count: u8,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McSlip39Shares> {
+    ffi_boundary_with_error(out_error, || {
+        let passphrase = Option::<&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+
+        split_secret(
+            &master_secret,
+            passphrase.unwrap_or(""),
+            threshold,
+            share_count,
+        )
+    })
+}
+
+fn split_secret(
+    master_secret: &[u8],
+    passphrase: &str,
+    threshold: u8,
+    share_count: u8,
+) -> Result<McSlip39Shares, LibMcError> {
+    let group_shares = sssmc39::generate_mnemonics(
+        1,
+        &[(threshold, share_count)],
+        master_secret,
+        passphrase,
+        ITERATION_EXPONENT,
+    )
+    .map_err(|err| LibMcError::InvalidInput(format!("{}", err)))?;
+
+    let mut shares = McSlip39Shares::new();
+    for group_share in group_shares {
+        let mnemonics = group_share
+            .mnemonic_list()
+            .map_err(|err| LibMcError::InvalidOutput(format!("{}", err)))?;
+        shares.extend(mnemonics.into_iter().map(|mut words| {
+            let share = Zeroizing::new(words.join(" "));
+            words.zeroize();
+            share
+        }));
+    }
+    Ok(shares)
+}
+
+/// Recovers the master secret from `shares`, which must contain at least the
+/// threshold number of shares from a single split.
+///
+/// # Preconditions
+///
+/// * `passphrase` - must be null or else a nul-terminated C string containing
+///   valid UTF-8.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput` - a share is malformed or fails its checksum,
+///   the shares come from different splits, or there are too few of them.
+#[no_mangle]
+pub extern "C" fn mc_slip39_combine_shares(
+    shares: FfiRefPtr<McSlip39Shares>,
+    passphrase: FfiOptStr,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McSecret> {
+    ffi_boundary_with_error(out_error, || {
+        let passphrase = Option::<&str>::try_from_ffi(passphrase).expect("passphrase is invalid");
+
+        combine_shares(&shares, passphrase.unwrap_or(""))
+    })
+}
+
+fn combine_shares(shares: &[Zeroizing<String>], passphrase: &str) -> Result<McSecret, LibMcError> {
+    let mut mnemonics: Vec<Vec<String>> = shares
+        .iter()
+        .map(|share| {
+            share
+                .split_whitespace()
+                .map(|word| word.to_lowercase())
+                .collect()
+        })
+        .collect();
+    let master_secret = sssmc39::combine_mnemonics(&mnemonics, passphrase)
+        .map_err(|err| LibMcError::InvalidInput(format!("{}", err)));
+    mnemonics.zeroize();
+    Ok(Zeroizing::new(master_secret?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER_SECRET: [u8; 16] = *b"slip39 test seed";
+
+    #[test]
+    fn any_threshold_of_shares_recovers_the_secret() {
+        let shares = split_secret(&MASTER_SECRET, "passphrase", 2, 3).unwrap();
+        assert_eq!(shares.len(), 3);
+
+        for pair in [[0, 1], [0, 2], [2, 1]] {
+            let subset = pair
+                .iter()
+                .map(|index| shares[*index].clone())
+                .collect::<Vec<_>>();
+            let master_secret = combine_shares(&subset, "passphrase").unwrap();
+            assert_eq!(master_secret.as_slice(), MASTER_SECRET);
+        }
+    }
+
+    #[test]
+    fn shares_are_combined_case_insensitively() {
+        let shares = split_secret(&MASTER_SECRET, "", 1, 1).unwrap();
+        let shares = vec![Zeroizing::new(shares[0].to_uppercase())];
+
+        let master_secret = combine_shares(&shares, "").unwrap();
+        assert_eq!(master_secret.as_slice(), MASTER_SECRET);
+    }
+
+    #[test]
+    fn too_few_shares_are_rejected() {
+        let shares = split_secret(&MASTER_SECRET, "", 2, 3).unwrap();
+        assert!(matches!(
+            combine_shares(&shares[..1], ""),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn the_wrong_passphrase_recovers_a_different_secret() {
+        let shares = split_secret(&MASTER_SECRET, "passphrase", 2, 3).unwrap();
+        let master_secret = combine_shares(&shares, "other passphrase").unwrap();
+        assert_ne!(master_secret.as_slice(), MASTER_SECRET);
+    }
+
+    #[test]
+    fn invalid_split_parameters_are_rejected() {
+        assert!(matches!(
+            split_secret(&MASTER_SECRET[..15], "", 2, 3),
+            Err(LibMcError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            split_secret(&MASTER_SECRET, "", 4, 3),
+            Err(LibMcError::InvalidInput(_))
+        ));
+    }
+}
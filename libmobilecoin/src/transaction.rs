@@ -3,8 +3,9 @@
 
 use crate::{
     common::*,
-    fog::McFogResolver,
-    keys::{McAccountKey, McPublicAddress},
+    crypto::ristretto_private_from_secret,
+    fog::{McFogResolver, SdkFogResolver},
+    keys::{McAccountKey, McAccountKeyHandle, McPublicAddress},
     LibMcError,
 };
 use core::convert::TryFrom;
@@ -13,7 +14,7 @@ use generic_array::{typenum::U66, GenericArray};
 use mc_account_keys::{AccountKey, PublicAddress, ShortAddressHash};
 use mc_crypto_keys::{CompressedRistrettoPublic, ReprBytes, RistrettoPrivate, RistrettoPublic};
 use mc_crypto_ring_signature_signer::NoKeysRingSigner;
-use mc_fog_report_validation::FogResolver;
+use mc_fog_report_validation::FogPubkeyResolver;
 use mc_transaction_core::{
     get_tx_out_shared_secret,
     onetime_keys::{recover_onetime_private_key, recover_public_subaddress_spend_key},
@@ -166,21 +167,66 @@ pub extern "C" fn mc_tx_out_matches_subaddress(
             RistrettoPrivate::try_from_ffi(&subaddress_spend_private_key)
                 .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
 
-        let mut matches = false;
-        if let Ok(target_key) = RistrettoPublic::try_from_ffi(&tx_out_target_key) {
-            if let Ok(tx_out_public_key) = RistrettoPublic::try_from_ffi(&tx_out_public_key) {
-                let onetime_private_key = recover_onetime_private_key(
-                    &tx_out_public_key,
-                    &view_private_key,
-                    &subaddress_spend_private_key,
-                );
-                matches = RistrettoPublic::from(&onetime_private_key) == target_key;
-            }
-        }
-        *out_matches.into_mut() = matches;
+        *out_matches.into_mut() = tx_out_matches_subaddress(
+            &tx_out_target_key,
+            &tx_out_public_key,
+            &view_private_key,
+            &subaddress_spend_private_key,
+        );
     })
 }
 
+/// Like `mc_tx_out_matches_subaddress`, but takes the private keys as secrets.
+///
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte
+///   Ristretto-format scalar.
+#[no_mangle]
+pub extern "C" fn mc_tx_out_matches_subaddress_with_secrets(
+    tx_out_target_key: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    view_private_key: FfiRefPtr<McSecret>,
+    subaddress_spend_private_key: FfiRefPtr<McSecret>,
+    out_matches: FfiMutPtr<bool>,
+) -> bool {
+    ffi_boundary(|| {
+        let view_private_key = ristretto_private_from_secret(&view_private_key)
+            .expect("view_private_key is not a valid RistrettoPrivate");
+        let subaddress_spend_private_key =
+            ristretto_private_from_secret(&subaddress_spend_private_key)
+                .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
+
+        *out_matches.into_mut() = tx_out_matches_subaddress(
+            &tx_out_target_key,
+            &tx_out_public_key,
+            &view_private_key,
+            &subaddress_spend_private_key,
+        );
+    })
+}
+
+fn tx_out_matches_subaddress(
+    tx_out_target_key: &McBuffer,
+    tx_out_public_key: &McBuffer,
+    view_private_key: &RistrettoPrivate,
+    subaddress_spend_private_key: &RistrettoPrivate,
+) -> bool {
+    let mut matches = false;
+    if let Ok(target_key) = RistrettoPublic::try_from_ffi(tx_out_target_key) {
+        if let Ok(tx_out_public_key) = RistrettoPublic::try_from_ffi(tx_out_public_key) {
+            let onetime_private_key = recover_onetime_private_key(
+                &tx_out_public_key,
+                view_private_key,
+                subaddress_spend_private_key,
+            );
+            matches = RistrettoPublic::from(&onetime_private_key) == target_key;
+        }
+    }
+    matches
+}
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -268,35 +314,91 @@ pub extern "C" fn mc_tx_out_get_key_image(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> bool {
     ffi_boundary_with_error(out_error, || {
-        let target_key = RistrettoPublic::try_from_ffi(&tx_out_target_key)?;
-        let tx_out_public_key = RistrettoPublic::try_from_ffi(&tx_out_public_key)?;
         let view_private_key = RistrettoPrivate::try_from_ffi(&view_private_key)
             .expect("view_private_key is not a valid RistrettoPrivate");
         let subaddress_spend_private_key =
             RistrettoPrivate::try_from_ffi(&subaddress_spend_private_key)
                 .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
-        let out_key_image = out_key_image
-            .into_mut()
-            .as_slice_mut_of_len(KeyImage::size())
-            .expect("out_key_image length is insufficient");
 
-        let onetime_private_key = recover_onetime_private_key(
+        tx_out_key_image(
+            &tx_out_target_key,
             &tx_out_public_key,
             &view_private_key,
             &subaddress_spend_private_key,
-        );
-        if RistrettoPublic::from(&onetime_private_key) != target_key {
-            return Err(LibMcError::TransactionCrypto(
-                "TxOut is not owned by private keys".to_owned(),
-            ));
-        }
-        let key_image = KeyImage::from(&onetime_private_key);
+            out_key_image,
+        )
+    })
+}
 
-        out_key_image.copy_from_slice(key_image.as_ref());
-        Ok(())
+/// Like `mc_tx_out_get_key_image`, but takes the private keys as secrets.
+///
+/// # Preconditions
+///
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte
+///   Ristretto-format scalar.
+/// * `out_key_image` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+/// * `LibMcError::TransactionCrypto`
+#[no_mangle]
+pub extern "C" fn mc_tx_out_get_key_image_with_secrets(
+    tx_out_target_key: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    view_private_key: FfiRefPtr<McSecret>,
+    subaddress_spend_private_key: FfiRefPtr<McSecret>,
+    out_key_image: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let view_private_key = ristretto_private_from_secret(&view_private_key)
+            .expect("view_private_key is not a valid RistrettoPrivate");
+        let subaddress_spend_private_key =
+            ristretto_private_from_secret(&subaddress_spend_private_key)
+                .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
+
+        tx_out_key_image(
+            &tx_out_target_key,
+            &tx_out_public_key,
+            &view_private_key,
+            &subaddress_spend_private_key,
+            out_key_image,
+        )
     })
 }
 
+fn tx_out_key_image(
+    tx_out_target_key: &McBuffer,
+    tx_out_public_key: &McBuffer,
+    view_private_key: &RistrettoPrivate,
+    subaddress_spend_private_key: &RistrettoPrivate,
+    out_key_image: FfiMutPtr<McMutableBuffer>,
+) -> Result<(), LibMcError> {
+    let target_key = RistrettoPublic::try_from_ffi(tx_out_target_key)?;
+    let tx_out_public_key = RistrettoPublic::try_from_ffi(tx_out_public_key)?;
+    let out_key_image = out_key_image
+        .into_mut()
+        .as_slice_mut_of_len(KeyImage::size())
+        .expect("out_key_image length is insufficient");
+
+    let onetime_private_key = recover_onetime_private_key(
+        &tx_out_public_key,
+        view_private_key,
+        subaddress_spend_private_key,
+    );
+    if RistrettoPublic::from(&onetime_private_key) != target_key {
+        return Err(LibMcError::TransactionCrypto(
+            "TxOut is not owned by private keys".to_owned(),
+        ));
+    }
+    let key_image = KeyImage::from(&onetime_private_key);
+
+    out_key_image.copy_from_slice(key_image.as_ref());
+    Ok(())
+}
+
 /// # Preconditions
 ///
 /// * `view_private_key` - must be a valid 32-byte Ristretto-format scalar.
@@ -368,9 +470,99 @@ pub extern "C" fn mc_transaction_builder_ring_add_element(
 
 /* ==== McTransactionBuilder ==== */
 
-pub type McTransactionBuilder = Option<TransactionBuilder<FogResolver>>;
-impl_into_ffi!(Option<TransactionBuilder<FogResolver>>);
+/// A `TransactionBuilder` that checks each output against the fog pubkey
+/// expiry of its recipient as it is added.
+pub struct TransactionBuilderHandle {
+    /// `None` once the builder has been consumed by a call to `build`.
+    transaction_builder: Option<TransactionBuilder<SdkFogResolver>>,
+    /// Resolves the same fog pubkeys as the builder, to look up their expiry.
+    fog_resolver: SdkFogResolver,
+    /// The builder's tombstone block, capped at the fog pubkey expiry of each
+    /// recipient added so far.
+    tombstone_block: u64,
+    current_block_height: u64,
+    /// The amounts of the inputs added so far.
+    input_amounts: Vec<Amount>,
+}
 
+impl TransactionBuilderHandle {
+    pub(crate) fn new(
+        mut transaction_builder: TransactionBuilder<SdkFogResolver>,
+        fog_resolver: SdkFogResolver,
+        tombstone_block: u64,
+        current_block_height: u64,
+    ) -> Self {
+        transaction_builder.set_tombstone_block(tombstone_block);
+        Self {
+            transaction_builder: Some(transaction_builder),
+            fog_resolver,
+            tombstone_block,
+            current_block_height,
+            input_amounts: Vec::new(),
+        }
+    }
+
+    pub(crate) fn builder_mut(&mut self) -> &mut TransactionBuilder<SdkFogResolver> {
+        self.transaction_builder
+            .as_mut()
+            .expect("McTransactionBuilder instance has already been used to build a Tx")
+    }
+
+    /// The total value of the inputs added so far in `token_id`.
+    pub(crate) fn input_value(&self, token_id: TokenId) -> Result<u64, LibMcError> {
+        self.input_amounts
+            .iter()
+            .filter(|amount| amount.token_id == token_id)
+            .try_fold(0u64, |total, amount| total.checked_add(amount.value))
+            .ok_or_else(|| LibMcError::InvalidInput("Input values overflow".to_owned()))
+    }
+
+    fn take_builder(&mut self) -> TransactionBuilder<SdkFogResolver> {
+        self.transaction_builder
+            .take()
+            .expect("McTransactionBuilder instance has already been used to build a Tx")
+    }
+
+    /// Fails if the fog pubkey of `recipient` expires at or before the current
+    /// block height, since an output to it could never land. Otherwise caps
+    /// the tombstone block at that expiry, so that fog can index the output.
+    ///
+    /// Recipients without fog always pass. Fog pubkeys that cannot be resolved
+    /// are left for the builder to report when the output is added.
+    pub(crate) fn apply_fog_pubkey_expiry(
+        &mut self,
+        recipient: &PublicAddress,
+    ) -> Result<(), LibMcError> {
+        if recipient.fog_report_url().is_none() {
+            return Ok(());
+        }
+        let pubkey_expiry = match self.fog_resolver.get_fog_pubkey(recipient) {
+            Ok(fog_pubkey) => fog_pubkey.pubkey_expiry,
+            Err(_) => return Ok(()),
+        };
+        if pubkey_expiry <= self.current_block_height {
+            return Err(LibMcError::FogPubkey(format!(
+                "Fog pubkey expired at block {}, current block height is {}: refresh the fog \
+                 report",
+                pubkey_expiry, self.current_block_height
+            )));
+        }
+        if pubkey_expiry < self.tombstone_block {
+            self.tombstone_block = pubkey_expiry;
+            self.builder_mut().set_tombstone_block(pubkey_expiry);
+        }
+        Ok(())
+    }
+}
+
+pub type McTransactionBuilder = TransactionBuilderHandle;
+impl_into_ffi!(TransactionBuilderHandle);
+
+/// `current_block_height` is the height of the ledger as last seen by the
+/// app. Each output added to the builder is checked against the fog pubkey
+/// expiry of its recipient: an output whose recipient's fog pubkey expires at
+/// or before `current_block_height` is rejected, and otherwise the tombstone
+/// block is capped at that expiry so that fog can index the output.
 ///
 /// # Errors
 ///
@@ -380,6 +572,7 @@ pub extern "C" fn mc_transaction_builder_create(
     fee: u64,
     token_id: u64,
     tombstone_block: u64,
+    current_block_height: u64,
     fog_resolver: FfiOptRefPtr<McFogResolver>,
     memo_builder: FfiMutPtr<McTxOutMemoBuilder>,
     block_version: u32,
@@ -389,11 +582,12 @@ pub extern "C" fn mc_transaction_builder_create(
         let fog_resolver =
             fog_resolver
                 .as_ref()
-                .map_or_else(FogResolver::default, |fog_resolver| {
+                .map_or_else(SdkFogResolver::default, |fog_resolver| {
                     // It is safe to add an expect here (which should never occur) because
                     // fogReportUrl is already checked in mc_fog_resolver_add_report_response
                     // to be convertible to FogUri
-                    FogResolver::new(fog_resolver.0.clone(), &fog_resolver.1)
+                    fog_resolver
+                        .fog_resolver()
                         .expect("FogResolver could not be constructed from the provided materials")
                 });
         let block_version = BlockVersion::try_from(block_version)?;
@@ -405,16 +599,20 @@ pub extern "C" fn mc_transaction_builder_create(
 
         let fee_amount = Amount::new(fee, TokenId::from(token_id));
 
-        let mut transaction_builder = TransactionBuilder::new_with_box(
+        let transaction_builder = TransactionBuilder::new_with_box(
             block_version,
             fee_amount,
-            fog_resolver,
+            fog_resolver.clone(),
             memo_builder_box,
         )
         .expect("failure not expected");
 
-        transaction_builder.set_tombstone_block(tombstone_block);
-        Ok(Some(transaction_builder))
+        Ok(TransactionBuilderHandle::new(
+            transaction_builder,
+            fog_resolver,
+            tombstone_block,
+            current_block_height,
+        ))
     })
 }
 
@@ -450,48 +648,106 @@ pub extern "C" fn mc_transaction_builder_add_input(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> bool {
     ffi_boundary_with_error(out_error, || {
-        let transaction_builder = transaction_builder
-            .into_mut()
-            .as_mut()
-            .expect("McTransactionBuilder instance has already been used to build a Tx");
         let view_private_key = RistrettoPrivate::try_from_ffi(&view_private_key)
             .expect("view_private_key is not a valid RistrettoPrivate");
         let subaddress_spend_private_key =
             RistrettoPrivate::try_from_ffi(&subaddress_spend_private_key)
                 .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
-        let membership_proofs = ring.iter().map(|element| element.1.clone()).collect();
-        let ring: Vec<TxOut> = ring.iter().map(|element| element.0.clone()).collect();
-        let input_tx_out = ring
-            .get(real_index)
-            .expect("real_index not in bounds of ring")
-            .clone();
-        let target_key = RistrettoPublic::try_from(&input_tx_out.target_key)
-            .expect("input_tx_out.target_key is not a valid RistrettoPublic");
-        let public_key = RistrettoPublic::try_from(&input_tx_out.public_key)
-            .expect("input_tx_out.public_key is not a valid RistrettoPublic");
-
-        let onetime_private_key = recover_onetime_private_key(
-            &public_key,
-            &view_private_key,
+
+        add_input(
+            transaction_builder,
+            view_private_key,
             &subaddress_spend_private_key,
-        );
-        if RistrettoPublic::from(&onetime_private_key) != target_key {
-            panic!("TxOut at real_index isn't owned by account key");
-        }
-        let input_credential = InputCredentials::new(
-            ring,
-            membership_proofs,
             real_index,
-            onetime_private_key,
-            view_private_key, // `a`
+            &ring,
         )
-        .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
-        transaction_builder.add_input(input_credential);
+    })
+}
 
-        Ok(())
+/// Like `mc_transaction_builder_add_input`, but takes the private keys as
+/// secrets.
+///
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call
+///   to `build`.
+/// * `view_private_key` - must hold a valid 32-byte Ristretto-format scalar.
+/// * `subaddress_spend_private_key` - must hold a valid 32-byte
+///   Ristretto-format scalar.
+/// * `real_index` - must be within bounds of `ring`.
+/// * `ring` - `TxOut` at `real_index` must be owned by account keys.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_transaction_builder_add_input_with_secrets(
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    view_private_key: FfiRefPtr<McSecret>,
+    subaddress_spend_private_key: FfiRefPtr<McSecret>,
+    real_index: usize,
+    ring: FfiRefPtr<McTransactionBuilderRing>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        let view_private_key = ristretto_private_from_secret(&view_private_key)
+            .expect("view_private_key is not a valid RistrettoPrivate");
+        let subaddress_spend_private_key =
+            ristretto_private_from_secret(&subaddress_spend_private_key)
+                .expect("subaddress_spend_private_key is not a valid RistrettoPrivate");
+
+        add_input(
+            transaction_builder,
+            view_private_key,
+            &subaddress_spend_private_key,
+            real_index,
+            &ring,
+        )
     })
 }
 
+fn add_input(
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    view_private_key: RistrettoPrivate,
+    subaddress_spend_private_key: &RistrettoPrivate,
+    real_index: usize,
+    ring: &McTransactionBuilderRing,
+) -> Result<(), LibMcError> {
+    let transaction_builder = transaction_builder.into_mut();
+    let membership_proofs = ring.iter().map(|element| element.1.clone()).collect();
+    let ring: Vec<TxOut> = ring.iter().map(|element| element.0.clone()).collect();
+    let input_tx_out = ring
+        .get(real_index)
+        .expect("real_index not in bounds of ring")
+        .clone();
+    let target_key = RistrettoPublic::try_from(&input_tx_out.target_key)
+        .expect("input_tx_out.target_key is not a valid RistrettoPublic");
+    let public_key = RistrettoPublic::try_from(&input_tx_out.public_key)
+        .expect("input_tx_out.public_key is not a valid RistrettoPublic");
+
+    let onetime_private_key =
+        recover_onetime_private_key(&public_key, &view_private_key, subaddress_spend_private_key);
+    if RistrettoPublic::from(&onetime_private_key) != target_key {
+        panic!("TxOut at real_index isn't owned by account key");
+    }
+    let shared_secret = get_tx_out_shared_secret(&view_private_key, &public_key);
+    let (amount, _) = input_tx_out.masked_amount.get_value(&shared_secret)?;
+    let input_credential = InputCredentials::new(
+        ring,
+        membership_proofs,
+        real_index,
+        onetime_private_key,
+        view_private_key, // `a`
+    )
+    .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))?;
+    transaction_builder
+        .builder_mut()
+        .add_input(input_credential);
+    transaction_builder.input_amounts.push(amount);
+
+    Ok(())
+}
+
 /// # Preconditions
 ///
 /// * `transaction_builder` - must not have been previously consumed by a call
@@ -502,6 +758,9 @@ pub extern "C" fn mc_transaction_builder_add_input(
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 #[no_mangle]
 pub extern "C" fn mc_transaction_builder_add_output(
@@ -514,12 +773,11 @@ pub extern "C" fn mc_transaction_builder_add_output(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> FfiOptOwnedPtr<McData> {
     ffi_boundary_with_error(out_error, || {
-        let transaction_builder = transaction_builder
-            .into_mut()
-            .as_mut()
-            .expect("McTransactionBuilder instance has already been used to build a Tx");
+        let transaction_builder = transaction_builder.into_mut();
         let recipient_address =
             PublicAddress::try_from_ffi(&recipient_address).expect("recipient_address is invalid");
+        transaction_builder.apply_fog_pubkey_expiry(&recipient_address)?;
+        let transaction_builder = transaction_builder.builder_mut();
         let mut rng = SdkRng::from_ffi(rng_callback);
 
         let out_tx_out_confirmation_number = out_tx_out_confirmation_number
@@ -560,6 +818,9 @@ pub extern "C" fn mc_transaction_builder_add_output(
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 #[no_mangle]
 pub extern "C" fn mc_transaction_builder_add_change_output(
@@ -574,40 +835,91 @@ pub extern "C" fn mc_transaction_builder_add_change_output(
     ffi_boundary_with_error(out_error, || {
         let account_key_obj =
             AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
-        let transaction_builder = transaction_builder
-            .into_mut()
-            .as_mut()
-            .expect("McTransactionBuilder instance has already been used to build a Tx");
-        let change_destination = ReservedSubaddresses::from(&account_key_obj);
-        let mut rng = SdkRng::from_ffi(rng_callback);
-
-        let out_tx_out_confirmation_number = out_tx_out_confirmation_number
-            .into_mut()
-            .as_slice_mut_of_len(TxOutConfirmationNumber::size())
-            .expect("out_tx_out_confirmation_number length is insufficient");
-
-        // TODO (GH #1867): If you want to support mixed transactions, use something
-        // other than fee_token_id here.
-        let amount = Amount {
-            value: amount,
-            token_id: transaction_builder.get_fee_token_id(),
-        };
-
-        let out_tx_out_shared_secret = out_tx_out_shared_secret
-            .into_mut()
-            .as_slice_mut_of_len(RistrettoPublic::size())
-            .expect("out_tx_out_shared_secret length is insufficient");
-
-        let tx_out_context =
-            transaction_builder.add_change_output(amount, &change_destination, &mut rng)?;
-
-        out_tx_out_confirmation_number.copy_from_slice(tx_out_context.confirmation.as_ref());
-        out_tx_out_shared_secret.copy_from_slice(&tx_out_context.shared_secret.to_bytes());
+        add_change_output(
+            &ReservedSubaddresses::from(&account_key_obj),
+            transaction_builder,
+            amount,
+            rng_callback,
+            out_tx_out_confirmation_number,
+            out_tx_out_shared_secret,
+        )
+    })
+}
 
-        Ok(mc_util_serial::encode(&tx_out_context.tx_out))
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call
+///   to `build`.
+/// * `out_tx_out_confirmation_number` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_transaction_builder_add_change_output_with_handle(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    amount: u64,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_tx_out_confirmation_number: FfiMutPtr<McMutableBuffer>,
+    out_tx_out_shared_secret: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        add_change_output(
+            account_key_handle.reserved_subaddresses(),
+            transaction_builder,
+            amount,
+            rng_callback,
+            out_tx_out_confirmation_number,
+            out_tx_out_shared_secret,
+        )
     })
 }
 
+fn add_change_output(
+    change_destination: &ReservedSubaddresses,
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    amount: u64,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_tx_out_confirmation_number: FfiMutPtr<McMutableBuffer>,
+    out_tx_out_shared_secret: FfiMutPtr<McMutableBuffer>,
+) -> Result<Vec<u8>, LibMcError> {
+    let transaction_builder = transaction_builder.into_mut();
+    transaction_builder.apply_fog_pubkey_expiry(&change_destination.change_subaddress)?;
+    let transaction_builder = transaction_builder.builder_mut();
+    let mut rng = SdkRng::from_ffi(rng_callback);
+
+    let out_tx_out_confirmation_number = out_tx_out_confirmation_number
+        .into_mut()
+        .as_slice_mut_of_len(TxOutConfirmationNumber::size())
+        .expect("out_tx_out_confirmation_number length is insufficient");
+
+    // TODO (GH #1867): If you want to support mixed transactions, use something
+    // other than fee_token_id here.
+    let amount = Amount {
+        value: amount,
+        token_id: transaction_builder.get_fee_token_id(),
+    };
+
+    let out_tx_out_shared_secret = out_tx_out_shared_secret
+        .into_mut()
+        .as_slice_mut_of_len(RistrettoPublic::size())
+        .expect("out_tx_out_shared_secret length is insufficient");
+
+    let tx_out_context =
+        transaction_builder.add_change_output(amount, change_destination, &mut rng)?;
+
+    out_tx_out_confirmation_number.copy_from_slice(tx_out_context.confirmation.as_ref());
+    out_tx_out_shared_secret.copy_from_slice(&tx_out_context.shared_secret.to_bytes());
+
+    Ok(mc_util_serial::encode(&tx_out_context.tx_out))
+}
+
 /// # Preconditions
 ///
 /// * `account_key` - must be a valid account key as the gift code subaddress is
@@ -619,6 +931,9 @@ pub extern "C" fn mc_transaction_builder_add_change_output(
 /// # Errors
 ///
 /// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
 /// * `LibMcError::InvalidInput`
 #[no_mangle]
 pub extern "C" fn mc_transaction_builder_fund_gift_code_output(
@@ -632,33 +947,80 @@ pub extern "C" fn mc_transaction_builder_fund_gift_code_output(
     ffi_boundary_with_error(out_error, || {
         let account_key_obj =
             AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
-        let transaction_builder = transaction_builder
-            .into_mut()
-            .as_mut()
-            .expect("McTransactionBuilder instance has already been used to build a Tx");
-        let reserved_subaddresses = ReservedSubaddresses::from(&account_key_obj);
-        let mut rng = SdkRng::from_ffi(rng_callback);
-        let out_tx_out_confirmation_number = out_tx_out_confirmation_number
-            .into_mut()
-            .as_slice_mut_of_len(TxOutConfirmationNumber::size())
-            .expect("out_tx_out_confirmation_number length is insufficient");
-
-        // TODO (GH #1867): If you want to support mixed transactions, use something
-        // other than fee_token_id here. A token_id arg will probably become necessary
-        // prior to release 1.3.0.
-        let amount = Amount {
-            value: amount,
-            token_id: transaction_builder.get_fee_token_id(),
-        };
-
-        let tx_out_context =
-            transaction_builder.add_gift_code_output(amount, &reserved_subaddresses, &mut rng)?;
+        fund_gift_code_output(
+            &ReservedSubaddresses::from(&account_key_obj),
+            transaction_builder,
+            amount,
+            rng_callback,
+            out_tx_out_confirmation_number,
+        )
+    })
+}
 
-        out_tx_out_confirmation_number.copy_from_slice(tx_out_context.confirmation.as_ref());
-        Ok(mc_util_serial::encode(&tx_out_context.tx_out))
+/// # Preconditions
+///
+/// * `transaction_builder` - must not have been previously consumed by a call
+///   to `build`.
+/// * `out_tx_out_confirmation_number` - length must be >= 32.
+///
+/// # Errors
+///
+/// * `LibMcError::AttestationVerification`
+/// * `LibMcError::FogPubkey` - the recipient's fog pubkey expires at or before
+///   the `current_block_height` the builder was created with. The fog report
+///   should be fetched again before adding the output.
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_transaction_builder_fund_gift_code_output_with_handle(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    amount: u64,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_tx_out_confirmation_number: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> FfiOptOwnedPtr<McData> {
+    ffi_boundary_with_error(out_error, || {
+        fund_gift_code_output(
+            account_key_handle.reserved_subaddresses(),
+            transaction_builder,
+            amount,
+            rng_callback,
+            out_tx_out_confirmation_number,
+        )
     })
 }
 
+fn fund_gift_code_output(
+    reserved_subaddresses: &ReservedSubaddresses,
+    transaction_builder: FfiMutPtr<McTransactionBuilder>,
+    amount: u64,
+    rng_callback: FfiOptMutPtr<McRngCallback>,
+    out_tx_out_confirmation_number: FfiMutPtr<McMutableBuffer>,
+) -> Result<Vec<u8>, LibMcError> {
+    let transaction_builder = transaction_builder.into_mut();
+    transaction_builder.apply_fog_pubkey_expiry(&reserved_subaddresses.gift_code_subaddress)?;
+    let transaction_builder = transaction_builder.builder_mut();
+    let mut rng = SdkRng::from_ffi(rng_callback);
+    let out_tx_out_confirmation_number = out_tx_out_confirmation_number
+        .into_mut()
+        .as_slice_mut_of_len(TxOutConfirmationNumber::size())
+        .expect("out_tx_out_confirmation_number length is insufficient");
+
+    // TODO (GH #1867): If you want to support mixed transactions, use something
+    // other than fee_token_id here. A token_id arg will probably become necessary
+    // prior to release 1.3.0.
+    let amount = Amount {
+        value: amount,
+        token_id: transaction_builder.get_fee_token_id(),
+    };
+
+    let tx_out_context =
+        transaction_builder.add_gift_code_output(amount, reserved_subaddresses, &mut rng)?;
+
+    out_tx_out_confirmation_number.copy_from_slice(tx_out_context.confirmation.as_ref());
+    Ok(mc_util_serial::encode(&tx_out_context.tx_out))
+}
+
 /// # Preconditions
 ///
 /// * `transaction_builder` - must not have been previously consumed by a call
@@ -674,10 +1036,7 @@ pub extern "C" fn mc_transaction_builder_build(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> FfiOptOwnedPtr<McData> {
     ffi_boundary_with_error(out_error, || {
-        let transaction_builder = transaction_builder
-            .into_mut()
-            .take()
-            .expect("McTransactionBuilder instance has already been used to build a Tx");
+        let transaction_builder = transaction_builder.into_mut().take_builder();
         let mut rng = SdkRng::from_ffi(rng_callback);
 
         let tx = transaction_builder
@@ -698,16 +1057,20 @@ pub extern "C" fn mc_memo_builder_sender_and_destination_create(
 ) -> FfiOptOwnedPtr<McTxOutMemoBuilder> {
     ffi_boundary(|| {
         let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
-        let mut rth_memo_builder: RTHMemoBuilder = RTHMemoBuilder::default();
-        rth_memo_builder.set_sender_credential(SenderMemoCredential::from(&account_key));
-        rth_memo_builder.enable_destination_memo();
-
-        let memo_builder_box: Box<dyn MemoBuilder + Sync + Send> = Box::new(rth_memo_builder);
-
-        Some(memo_builder_box)
+        sender_and_destination_memo_builder(&account_key, None)
     })
 }
 
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+#[no_mangle]
+pub extern "C" fn mc_memo_builder_sender_and_destination_create_with_handle(
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+) -> FfiOptOwnedPtr<McTxOutMemoBuilder> {
+    ffi_boundary(|| sender_and_destination_memo_builder(account_key_handle.account_key(), None))
+}
+
 /// # Preconditions
 ///
 /// * `account_key` - must be a valid `AccountKey` with `fog_info`.
@@ -718,15 +1081,40 @@ pub extern "C" fn mc_memo_builder_sender_payment_request_and_destination_create(
 ) -> FfiOptOwnedPtr<McTxOutMemoBuilder> {
     ffi_boundary(|| {
         let account_key = AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
-        let mut rth_memo_builder: RTHMemoBuilder = RTHMemoBuilder::default();
-        rth_memo_builder.set_sender_credential(SenderMemoCredential::from(&account_key));
+        sender_and_destination_memo_builder(&account_key, Some(payment_request_id))
+    })
+}
+
+/// # Preconditions
+///
+/// * `account_key_handle` - must have been created with fog info.
+#[no_mangle]
+pub extern "C" fn mc_memo_builder_sender_payment_request_and_destination_create_with_handle(
+    payment_request_id: u64,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+) -> FfiOptOwnedPtr<McTxOutMemoBuilder> {
+    ffi_boundary(|| {
+        sender_and_destination_memo_builder(
+            account_key_handle.account_key(),
+            Some(payment_request_id),
+        )
+    })
+}
+
+fn sender_and_destination_memo_builder(
+    account_key: &AccountKey,
+    payment_request_id: Option<u64>,
+) -> McTxOutMemoBuilder {
+    let mut rth_memo_builder: RTHMemoBuilder = RTHMemoBuilder::default();
+    rth_memo_builder.set_sender_credential(SenderMemoCredential::from(account_key));
+    if let Some(payment_request_id) = payment_request_id {
         rth_memo_builder.set_payment_request_id(payment_request_id);
-        rth_memo_builder.enable_destination_memo();
+    }
+    rth_memo_builder.enable_destination_memo();
 
-        let memo_builder_box: Box<dyn MemoBuilder + Sync + Send> = Box::new(rth_memo_builder);
+    let memo_builder_box: Box<dyn MemoBuilder + Sync + Send> = Box::new(rth_memo_builder);
 
-        Some(memo_builder_box)
-    })
+    Some(memo_builder_box)
 }
 
 #[no_mangle]
@@ -819,27 +1207,84 @@ pub extern "C" fn mc_memo_sender_memo_create(
     ffi_boundary_with_error(out_error, || {
         let sender_account_key =
             AccountKey::try_from_ffi(&sender_account_key).expect("account_key is invalid");
-        let recipient_subaddress_view_public_key =
-            RistrettoPublic::try_from_ffi(&recipient_subaddress_view_public_key)?;
-        let tx_out_public_key = CompressedRistrettoPublic::try_from_ffi(&tx_out_public_key)?;
+        sender_memo_create(
+            &sender_account_key,
+            recipient_subaddress_view_public_key,
+            tx_out_public_key,
+            None,
+            out_memo_data,
+        )
+    })
+}
 
-        let sender_cred = SenderMemoCredential::from(&sender_account_key);
-        let memo = AuthenticatedSenderMemo::new(
+/// # Preconditions
+///
+/// * `recipient_subaddress_view_public_key` - must be a valid 32-byte
+///   Ristretto-format scalar.
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_data` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_memo_sender_memo_create_with_handle(
+    sender_account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    recipient_subaddress_view_public_key: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    out_memo_data: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        sender_memo_create(
+            sender_account_key_handle.account_key(),
+            recipient_subaddress_view_public_key,
+            tx_out_public_key,
+            None,
+            out_memo_data,
+        )
+    })
+}
+
+/// Writes an `AuthenticatedSenderMemo`, or an
+/// `AuthenticatedSenderWithPaymentRequestIdMemo` when `payment_request_id` is
+/// set.
+fn sender_memo_create(
+    sender_account_key: &AccountKey,
+    recipient_subaddress_view_public_key: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    payment_request_id: Option<u64>,
+    out_memo_data: FfiMutPtr<McMutableBuffer>,
+) -> Result<(), LibMcError> {
+    let recipient_subaddress_view_public_key =
+        RistrettoPublic::try_from_ffi(&recipient_subaddress_view_public_key)?;
+    let tx_out_public_key = CompressedRistrettoPublic::try_from_ffi(&tx_out_public_key)?;
+
+    let sender_cred = SenderMemoCredential::from(sender_account_key);
+    let memo_bytes: [u8; 64] = if let Some(payment_request_id) = payment_request_id {
+        AuthenticatedSenderWithPaymentRequestIdMemo::new(
             &sender_cred,
             &recipient_subaddress_view_public_key,
             &tx_out_public_key,
-        );
+            payment_request_id,
+        )
+        .into()
+    } else {
+        AuthenticatedSenderMemo::new(
+            &sender_cred,
+            &recipient_subaddress_view_public_key,
+            &tx_out_public_key,
+        )
+        .into()
+    };
 
-        let memo_bytes: [u8; 64] = memo.into();
+    let out_memo_data = out_memo_data
+        .into_mut()
+        .as_slice_mut_of_len(core::mem::size_of_val(&memo_bytes))
+        .expect("out_memo_data length is insufficient");
 
-        let out_memo_data = out_memo_data
-            .into_mut()
-            .as_slice_mut_of_len(core::mem::size_of_val(&memo_bytes))
-            .expect("out_memo_data length is insufficient");
-
-        out_memo_data.copy_from_slice(&memo_bytes);
-        Ok(())
-    })
+    out_memo_data.copy_from_slice(&memo_bytes);
+    Ok(())
 }
 
 /// # Preconditions
@@ -1118,28 +1563,43 @@ pub extern "C" fn mc_memo_sender_with_payment_request_memo_create(
     ffi_boundary_with_error(out_error, || {
         let sender_account_key =
             AccountKey::try_from_ffi(&sender_account_key).expect("account_key is invalid");
-        let recipient_subaddress_view_public_key =
-            RistrettoPublic::try_from_ffi(&recipient_subaddress_view_public_key)?;
-        let tx_out_public_key = CompressedRistrettoPublic::try_from_ffi(&tx_out_public_key)?;
-
-        let sender_cred = SenderMemoCredential::from(&sender_account_key);
-        let memo = AuthenticatedSenderWithPaymentRequestIdMemo::new(
-            &sender_cred,
-            &recipient_subaddress_view_public_key,
-            &tx_out_public_key,
-            payment_request_id,
-        );
-
-        let memo_bytes: [u8; 64] = memo.into();
-
-        let out_memo_data = out_memo_data
-            .into_mut()
-            .as_slice_mut_of_len(core::mem::size_of_val(&memo_bytes))
-            .expect("out_memo_data length is insufficient");
-
-        out_memo_data.copy_from_slice(&memo_bytes);
+        sender_memo_create(
+            &sender_account_key,
+            recipient_subaddress_view_public_key,
+            tx_out_public_key,
+            Some(payment_request_id),
+            out_memo_data,
+        )
+    })
+}
 
-        Ok(())
+/// # Preconditions
+///
+/// * `recipient_subaddress_view_public_key` - must be a valid 32-byte
+///   Ristretto-format scalar.
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_data` - length must be >= 64.
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_memo_sender_with_payment_request_memo_create_with_handle(
+    sender_account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    recipient_subaddress_view_public_key: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    payment_request_id: u64,
+    out_memo_data: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        sender_memo_create(
+            sender_account_key_handle.account_key(),
+            recipient_subaddress_view_public_key,
+            tx_out_public_key,
+            Some(payment_request_id),
+            out_memo_data,
+        )
     })
 }
 
@@ -1577,26 +2037,67 @@ pub extern "C" fn mc_memo_decrypt_e_memo_payload(
     out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
 ) -> bool {
     ffi_boundary_with_error(out_error, || {
-        let tx_out_public_key = RistrettoPublic::try_from_ffi(&tx_out_public_key)?;
         let account_key_obj =
             AccountKey::try_from_ffi(&account_key).expect("account_key is invalid");
-        let e_memo = EncryptedMemo::try_from_ffi(&encrypted_memo)?;
-        let shared_secret =
-            get_tx_out_shared_secret(&*account_key_obj.view_private_key(), &tx_out_public_key);
-
-        let memo_payload: MemoPayload = e_memo.decrypt(&shared_secret);
-        let memo_payload_generic_array: GenericArray<u8, U66> = memo_payload.into();
-
-        let out_memo_payload = out_memo_payload
-            .into_mut()
-            .as_slice_mut_of_len(core::mem::size_of_val(&memo_payload_generic_array))
-            .expect("Memo payload length is insufficient");
+        decrypt_e_memo_payload(
+            encrypted_memo,
+            tx_out_public_key,
+            &account_key_obj,
+            out_memo_payload,
+        )
+    })
+}
 
-        out_memo_payload.copy_from_slice(&memo_payload_generic_array);
-        Ok(())
+/// # Preconditions
+///
+/// * `encrypted_memo` - must be 66 bytes
+/// * `tx_out_public_key` - must be a valid 32-byte Ristretto-format scalar.
+/// * `out_memo_payload` - length must be >= 16 bytes
+///
+/// # Errors
+///
+/// * `LibMcError::InvalidInput`
+#[no_mangle]
+pub extern "C" fn mc_memo_decrypt_e_memo_payload_with_handle(
+    encrypted_memo: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    account_key_handle: FfiRefPtr<McAccountKeyHandle>,
+    out_memo_payload: FfiMutPtr<McMutableBuffer>,
+    out_error: FfiOptMutPtr<FfiOptOwnedPtr<McError>>,
+) -> bool {
+    ffi_boundary_with_error(out_error, || {
+        decrypt_e_memo_payload(
+            encrypted_memo,
+            tx_out_public_key,
+            account_key_handle.account_key(),
+            out_memo_payload,
+        )
     })
 }
 
+fn decrypt_e_memo_payload(
+    encrypted_memo: FfiRefPtr<McBuffer>,
+    tx_out_public_key: FfiRefPtr<McBuffer>,
+    account_key: &AccountKey,
+    out_memo_payload: FfiMutPtr<McMutableBuffer>,
+) -> Result<(), LibMcError> {
+    let tx_out_public_key = RistrettoPublic::try_from_ffi(&tx_out_public_key)?;
+    let e_memo = EncryptedMemo::try_from_ffi(&encrypted_memo)?;
+    let shared_secret =
+        get_tx_out_shared_secret(&*account_key.view_private_key(), &tx_out_public_key);
+
+    let memo_payload: MemoPayload = e_memo.decrypt(&shared_secret);
+    let memo_payload_generic_array: GenericArray<u8, U66> = memo_payload.into();
+
+    let out_memo_payload = out_memo_payload
+        .into_mut()
+        .as_slice_mut_of_len(core::mem::size_of_val(&memo_payload_generic_array))
+        .expect("Memo payload length is insufficient");
+
+    out_memo_payload.copy_from_slice(&memo_payload_generic_array);
+    Ok(())
+}
+
 /********************************************************************
  * Trait Implementations
  */
@@ -1643,3 +2144,149 @@ impl<'a> TryFromFfi<&McBuffer<'a>> for EncryptedMemo {
             .map_err(|err| LibMcError::InvalidInput(format!("{:?}", err)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mc_transaction_std::EmptyMemoBuilder;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    fn transaction_builder_handle(current_block_height: u64) -> TransactionBuilderHandle {
+        let transaction_builder = TransactionBuilder::new_with_box(
+            BlockVersion::MAX,
+            Amount::new(10, TokenId::from(0)),
+            SdkFogResolver::default(),
+            Box::new(EmptyMemoBuilder::default()),
+        )
+        .unwrap();
+        TransactionBuilderHandle::new(
+            transaction_builder,
+            SdkFogResolver::default(),
+            1000,
+            current_block_height,
+        )
+    }
+
+    #[test]
+    fn recipients_without_fog_pass_the_expiry_check() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let recipient = AccountKey::random(&mut rng).default_subaddress();
+        let mut transaction_builder = transaction_builder_handle(100);
+
+        assert!(transaction_builder
+            .apply_fog_pubkey_expiry(&recipient)
+            .is_ok());
+        assert_eq!(transaction_builder.tombstone_block, 1000);
+    }
+
+    #[test]
+    fn unresolved_fog_pubkeys_are_left_to_the_builder() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let recipient = AccountKey::random_with_fog(&mut rng).default_subaddress();
+        let mut transaction_builder = transaction_builder_handle(100);
+
+        assert!(transaction_builder
+            .apply_fog_pubkey_expiry(&recipient)
+            .is_ok());
+        assert!(transaction_builder
+            .builder_mut()
+            .add_output(Amount::new(1, TokenId::from(0)), &recipient, &mut rng)
+            .is_err());
+    }
+
+    #[cfg(feature = "fake-fog-ingest")]
+    fn fake_fog_transaction_builder_handle(
+        rng: &mut ChaCha20Rng,
+        tombstone_block: u64,
+        current_block_height: u64,
+    ) -> (TransactionBuilderHandle, PublicAddress) {
+        use crate::{
+            fake_fog_ingest::{FakeFogIngest, TrustedFakeFogIngest},
+            fog::FogReportCache,
+        };
+        use mc_attest_verifier::Verifier;
+        use mc_util_uri::FogUri;
+        use std::str::FromStr;
+
+        let fake_fog_ingest = FakeFogIngest::new(rng);
+        let report_url = "fog://fog.example.com";
+        let recipient = AccountKey::new_with_fog(
+            &RistrettoPrivate::from_random(rng),
+            &RistrettoPrivate::from_random(rng),
+            report_url,
+            String::new(),
+            fake_fog_ingest.authority_spki(),
+        )
+        .default_subaddress();
+
+        let mut fog_report_cache = FogReportCache::new(Verifier::default());
+        fog_report_cache.add_report_response(
+            FogUri::from_str(report_url).unwrap().to_string(),
+            fake_fog_ingest.report_response("", 100).unwrap(),
+        );
+        fog_report_cache.trust_fake_fog_ingest(TrustedFakeFogIngest::from(&fake_fog_ingest));
+        let fog_resolver = fog_report_cache.fog_resolver().unwrap();
+        let transaction_builder = TransactionBuilder::new_with_box(
+            BlockVersion::MAX,
+            Amount::new(10, TokenId::from(0)),
+            fog_resolver.clone(),
+            Box::new(EmptyMemoBuilder::default()),
+        )
+        .unwrap();
+        let transaction_builder = TransactionBuilderHandle::new(
+            transaction_builder,
+            fog_resolver,
+            tombstone_block,
+            current_block_height,
+        );
+        (transaction_builder, recipient)
+    }
+
+    #[cfg(feature = "fake-fog-ingest")]
+    #[test]
+    fn expired_fog_pubkeys_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+        let (mut transaction_builder, recipient) =
+            fake_fog_transaction_builder_handle(&mut rng, 1000, 99);
+        assert!(transaction_builder
+            .apply_fog_pubkey_expiry(&recipient)
+            .is_ok());
+
+        let (mut transaction_builder, recipient) =
+            fake_fog_transaction_builder_handle(&mut rng, 1000, 100);
+        assert!(matches!(
+            transaction_builder.apply_fog_pubkey_expiry(&recipient),
+            Err(LibMcError::FogPubkey(_))
+        ));
+    }
+
+    #[cfg(feature = "fake-fog-ingest")]
+    #[test]
+    fn tombstone_block_is_capped_at_fog_pubkey_expiry() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+        let (mut transaction_builder, recipient) =
+            fake_fog_transaction_builder_handle(&mut rng, 1000, 50);
+        transaction_builder
+            .apply_fog_pubkey_expiry(&recipient)
+            .unwrap();
+        assert_eq!(transaction_builder.tombstone_block, 100);
+
+        let (mut transaction_builder, recipient) =
+            fake_fog_transaction_builder_handle(&mut rng, 80, 50);
+        transaction_builder
+            .apply_fog_pubkey_expiry(&recipient)
+            .unwrap();
+        assert_eq!(transaction_builder.tombstone_block, 80);
+    }
+
+    #[test]
+    #[should_panic(expected = "already been used to build a Tx")]
+    fn consumed_builder_cannot_be_used() {
+        let mut transaction_builder = transaction_builder_handle(0);
+        let _ = transaction_builder.take_builder();
+        transaction_builder.builder_mut();
+    }
+}